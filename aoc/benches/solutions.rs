@@ -0,0 +1,320 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Runs every day's solvers on both their sample and real inputs and writes the timings into
+// a json report. If a report from a previous run exists, it is used as the baseline to compare
+// against so that any regressions are immediately visible.
+//
+// usage: cargo bench -p aoc -- [filter] [--baseline <path>] [--report <path>]
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use utils::solution::Solution;
+
+const REAL_INPUT_FILE: &str = "input";
+const SAMPLE_INPUT_FILE: &str = "sample_input";
+
+const WARM_UP_TIME: Duration = Duration::from_millis(500);
+const MEASUREMENT_TIME: Duration = Duration::from_secs(3);
+// shortest time a single sample is allowed to take, otherwise timer resolution becomes an issue
+const MIN_SAMPLE_TIME: Duration = Duration::from_millis(1);
+const MIN_SAMPLES: u64 = 5;
+const MAX_SAMPLES: u64 = 100;
+
+// changes smaller than that are treated as noise
+const NOISE_THRESHOLD: f64 = 0.05;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Measurement {
+    id: String,
+    samples: usize,
+    iterations_per_sample: u64,
+    mean_ns: f64,
+    median_ns: f64,
+    min_ns: f64,
+    max_ns: f64,
+    std_dev_ns: f64,
+}
+
+impl Measurement {
+    fn new(id: String, iterations_per_sample: u64, mut sample_times: Vec<f64>) -> Self {
+        sample_times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let samples = sample_times.len();
+        let mean_ns = sample_times.iter().sum::<f64>() / samples as f64;
+        let median_ns = if samples.is_multiple_of(2) {
+            (sample_times[samples / 2 - 1] + sample_times[samples / 2]) / 2.0
+        } else {
+            sample_times[samples / 2]
+        };
+        let variance = sample_times
+            .iter()
+            .map(|time| (time - mean_ns).powi(2))
+            .sum::<f64>()
+            / samples as f64;
+
+        Measurement {
+            id,
+            samples,
+            iterations_per_sample,
+            mean_ns,
+            median_ns,
+            min_ns: sample_times[0],
+            max_ns: sample_times[samples - 1],
+            std_dev_ns: variance.sqrt(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Report {
+    benchmarks: Vec<Measurement>,
+}
+
+struct Bencher {
+    filter: Option<String>,
+    baseline: HashMap<String, Measurement>,
+    report: Report,
+}
+
+impl Bencher {
+    fn new(filter: Option<String>, baseline: Option<Report>) -> Self {
+        Bencher {
+            filter,
+            baseline: baseline
+                .map(|report| {
+                    report
+                        .benchmarks
+                        .into_iter()
+                        .map(|measurement| (measurement.id.clone(), measurement))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            report: Default::default(),
+        }
+    }
+
+    fn is_filtered_out(&self, id: &str) -> bool {
+        match &self.filter {
+            Some(filter) => !id.contains(filter.as_str()),
+            None => false,
+        }
+    }
+
+    fn bench<O, F: FnMut() -> O>(&mut self, id: String, mut routine: F) {
+        if self.is_filtered_out(&id) {
+            return;
+        }
+
+        // warm up, and while at it, figure out roughly how long a single iteration takes
+        let warm_up_start = Instant::now();
+        let mut warm_up_iterations = 0u32;
+        while warm_up_iterations == 0 || warm_up_start.elapsed() < WARM_UP_TIME {
+            black_box(routine());
+            warm_up_iterations += 1;
+        }
+        let iteration_time = warm_up_start.elapsed() / warm_up_iterations;
+
+        let iterations_per_sample =
+            (MIN_SAMPLE_TIME.as_nanos() / iteration_time.as_nanos().max(1)).max(1) as u64;
+        let sample_time = iteration_time.as_nanos() as u64 * iterations_per_sample;
+        let num_samples = (MEASUREMENT_TIME.as_nanos() as u64 / sample_time.max(1))
+            .clamp(MIN_SAMPLES, MAX_SAMPLES);
+
+        let sample_times = (0..num_samples)
+            .map(|_| {
+                let start = Instant::now();
+                for _ in 0..iterations_per_sample {
+                    black_box(routine());
+                }
+                start.elapsed().as_nanos() as f64 / iterations_per_sample as f64
+            })
+            .collect();
+
+        let measurement = Measurement::new(id, iterations_per_sample, sample_times);
+        self.print_measurement(&measurement);
+        self.report.benchmarks.push(measurement);
+    }
+
+    fn print_measurement(&self, measurement: &Measurement) {
+        print!(
+            "{:<24} time: [{} {} {}]",
+            measurement.id,
+            format_time(measurement.min_ns),
+            format_time(measurement.median_ns),
+            format_time(measurement.max_ns)
+        );
+
+        if let Some(previous) = self.baseline.get(&measurement.id) {
+            let change = (measurement.median_ns - previous.median_ns) / previous.median_ns;
+            let verdict = if change > NOISE_THRESHOLD {
+                "REGRESSED"
+            } else if change < -NOISE_THRESHOLD {
+                "improved"
+            } else {
+                "no change"
+            };
+            print!("  change: {:+.2}% ({})", change * 100.0, verdict);
+        }
+        println!();
+    }
+}
+
+fn format_time(ns: f64) -> String {
+    if ns < 1e3 {
+        format!("{:.2} ns", ns)
+    } else if ns < 1e6 {
+        format!("{:.2} µs", ns / 1e3)
+    } else if ns < 1e9 {
+        format!("{:.2} ms", ns / 1e6)
+    } else {
+        format!("{:.2} s", ns / 1e9)
+    }
+}
+
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the aoc crate lives inside the workspace")
+}
+
+fn day_directory(day: usize) -> PathBuf {
+    workspace_root().join(format!("day{}", day))
+}
+
+fn bench_solution<S: Solution>(bencher: &mut Bencher, day: usize) {
+    for (input_name, input_file) in &[("sample", SAMPLE_INPUT_FILE), ("real", REAL_INPUT_FILE)] {
+        let input_path = day_directory(day).join(input_file);
+        // not every day has a sample that could be used with the real input parameters
+        if !input_path.exists() {
+            continue;
+        }
+
+        let input = match S::parse_input(&input_path) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("skipping {} - {}", input_path.display(), err);
+                continue;
+            }
+        };
+
+        bencher.bench(format!("day{:02}/part1/{}", day, input_name), || {
+            S::part1(&input)
+        });
+        if S::HAS_PART2 {
+            bencher.bench(format!("day{:02}/part2/{}", day, input_name), || {
+                S::part2(&input)
+            });
+        }
+    }
+}
+
+fn run_benchmarks(bencher: &mut Bencher) {
+    bench_solution::<day1::Day1>(bencher, 1);
+    bench_solution::<day2::Day2>(bencher, 2);
+    bench_solution::<day3::Day3>(bencher, 3);
+    bench_solution::<day4::Day4>(bencher, 4);
+    bench_solution::<day5::Day5>(bencher, 5);
+    bench_solution::<day6::Day6>(bencher, 6);
+    bench_solution::<day7::Day7>(bencher, 7);
+    bench_solution::<day8::Day8>(bencher, 8);
+    // there is no sample_input as the example uses a preamble of 5 rather than 25 numbers
+    bench_solution::<day9::Day9>(bencher, 9);
+    bench_solution::<day10::Day10>(bencher, 10);
+    bench_solution::<day11::Day11>(bencher, 11);
+    bench_solution::<day12::Day12>(bencher, 12);
+    bench_solution::<day13::Day13>(bencher, 13);
+    bench_solution::<day14::Day14>(bencher, 14);
+    bench_solution::<day15::Day15>(bencher, 15);
+    bench_solution::<day16::Day16>(bencher, 16);
+    bench_solution::<day17::Day17>(bencher, 17);
+    bench_solution::<day18::Day18>(bencher, 18);
+    bench_solution::<day19::Day19>(bencher, 19);
+    bench_solution::<day20::Day20>(bencher, 20);
+    bench_solution::<day21::Day21>(bencher, 21);
+    bench_solution::<day22::Day22>(bencher, 22);
+    bench_solution::<day23::Day23>(bencher, 23);
+    bench_solution::<day24::Day24>(bencher, 24);
+    bench_solution::<day25::Day25>(bencher, 25);
+}
+
+fn default_report_path() -> PathBuf {
+    workspace_root()
+        .join("target")
+        .join("aoc-bench")
+        .join("report.json")
+}
+
+fn read_report(path: &Path) -> Option<Report> {
+    let raw = fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&raw) {
+        Ok(report) => Some(report),
+        Err(err) => {
+            eprintln!("ignoring malformed baseline {} - {}", path.display(), err);
+            None
+        }
+    }
+}
+
+fn main() {
+    let mut filter = None;
+    let mut baseline_path = None;
+    let mut report_path = default_report_path();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--baseline" => baseline_path = args.next().map(PathBuf::from),
+            "--report" => report_path = args.next().map(PathBuf::from).unwrap_or(report_path),
+            // cargo passes `--bench` (and possibly other flags) to every benchmark binary
+            flag if flag.starts_with("--") => (),
+            _ => filter = Some(arg),
+        }
+    }
+
+    // unless told otherwise, compare against whatever the previous run has produced
+    let baseline = read_report(baseline_path.as_ref().unwrap_or(&report_path));
+
+    let mut bencher = Bencher::new(filter, baseline);
+    run_benchmarks(&mut bencher);
+
+    // keep results of benchmarks that were filtered out in this run so that partial runs
+    // do not wipe the report
+    if let Some(previous) = read_report(&report_path) {
+        for measurement in previous.benchmarks {
+            if !bencher
+                .report
+                .benchmarks
+                .iter()
+                .any(|current| current.id == measurement.id)
+            {
+                bencher.report.benchmarks.push(measurement);
+            }
+        }
+        bencher.report.benchmarks.sort_by(|a, b| a.id.cmp(&b.id));
+    }
+
+    if let Some(parent) = report_path.parent() {
+        fs::create_dir_all(parent).expect("failed to create report directory");
+    }
+    let report =
+        serde_json::to_string_pretty(&bencher.report).expect("failed to serialize the report");
+    fs::write(&report_path, report).expect("failed to write the report");
+    println!("report written to {}", report_path.display());
+}
@@ -0,0 +1,281 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::env;
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::process;
+use utils::solution::Solution;
+
+const NUM_DAYS: usize = 25;
+const DEFAULT_INPUT_FILE: &str = "input";
+const USAGE: &str = "usage: aoc run <day|all> [--part <1|2>] [--input <path>]";
+
+#[derive(Debug, PartialEq)]
+enum DaySelection {
+    All,
+    Single(usize),
+}
+
+#[derive(Debug, PartialEq)]
+struct RunArguments {
+    days: DaySelection,
+    part: Option<usize>,
+    input: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+enum RunnerError {
+    InvalidArguments(String),
+    UnknownDay(usize),
+    MissingPart { day: usize, part: usize },
+    UnreadableInput { path: PathBuf, reason: String },
+    Unsolved { day: usize, part: usize },
+}
+
+impl Display for RunnerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RunnerError::InvalidArguments(reason) => write!(f, "invalid arguments - {}", reason),
+            RunnerError::UnknownDay(day) => write!(f, "there is no day {}", day),
+            RunnerError::MissingPart { day, part } => {
+                write!(f, "day {} does not have part {}", day, part)
+            }
+            RunnerError::UnreadableInput { path, reason } => {
+                write!(
+                    f,
+                    "failed to read input file {} - {}",
+                    path.display(),
+                    reason
+                )
+            }
+            RunnerError::Unsolved { day, part } => {
+                write!(f, "failed to solve part{} of day {}", part, day)
+            }
+        }
+    }
+}
+
+fn parse_arguments(args: &[String]) -> Result<RunArguments, RunnerError> {
+    let mut args = args.iter();
+    match args.next().map(|command| command.as_str()) {
+        Some("run") => (),
+        Some(command) => {
+            return Err(RunnerError::InvalidArguments(format!(
+                "unknown command {}",
+                command
+            )))
+        }
+        None => return Err(RunnerError::InvalidArguments("no command".to_string())),
+    }
+
+    let days = match args.next().map(|day| day.as_str()) {
+        Some("all") => DaySelection::All,
+        Some(raw_day) => {
+            let day = raw_day
+                .parse()
+                .map_err(|_| RunnerError::InvalidArguments(format!("invalid day {}", raw_day)))?;
+            if day == 0 || day > NUM_DAYS {
+                return Err(RunnerError::UnknownDay(day));
+            }
+            DaySelection::Single(day)
+        }
+        None => return Err(RunnerError::InvalidArguments("no day".to_string())),
+    };
+
+    let mut part = None;
+    let mut input = None;
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| RunnerError::InvalidArguments(format!("missing value for {}", flag)))?;
+        match flag.as_str() {
+            "--part" => match value.as_str() {
+                "1" => part = Some(1),
+                "2" => part = Some(2),
+                _ => {
+                    return Err(RunnerError::InvalidArguments(format!(
+                        "invalid part {}",
+                        value
+                    )))
+                }
+            },
+            "--input" => input = Some(PathBuf::from(value)),
+            _ => {
+                return Err(RunnerError::InvalidArguments(format!(
+                    "unknown flag {}",
+                    flag
+                )))
+            }
+        }
+    }
+
+    if days == DaySelection::All && input.is_some() {
+        return Err(RunnerError::InvalidArguments(
+            "custom input can only be used with a single day".to_string(),
+        ));
+    }
+
+    Ok(RunArguments { days, part, input })
+}
+
+// assumes the runner is executed from the root of the workspace
+fn default_input_path(day: usize) -> PathBuf {
+    Path::new(&format!("day{}", day)).join(DEFAULT_INPUT_FILE)
+}
+
+fn run_solution<S: Solution>(
+    day: usize,
+    input_path: &Path,
+    part: Option<usize>,
+) -> Result<(), RunnerError> {
+    if part == Some(2) && !S::HAS_PART2 {
+        return Err(RunnerError::MissingPart { day, part: 2 });
+    }
+
+    let input = S::parse_input(input_path).map_err(|err| RunnerError::UnreadableInput {
+        path: input_path.to_owned(),
+        reason: err.to_string(),
+    })?;
+    for warning in S::input_warnings(&input) {
+        eprintln!("day {} input - {}", day, warning);
+    }
+
+    if part != Some(2) {
+        let part1_result = S::part1(&input).ok_or(RunnerError::Unsolved { day, part: 1 })?;
+        println!("Part 1 result is {}", part1_result);
+    }
+
+    if part != Some(1) && S::HAS_PART2 {
+        let part2_result = S::part2(&input).ok_or(RunnerError::Unsolved { day, part: 2 })?;
+        println!("Part 2 result is {}", part2_result);
+    }
+
+    Ok(())
+}
+
+fn run_day(day: usize, input_path: &Path, part: Option<usize>) -> Result<(), RunnerError> {
+    match day {
+        1 => run_solution::<day1::Day1>(day, input_path, part),
+        2 => run_solution::<day2::Day2>(day, input_path, part),
+        3 => run_solution::<day3::Day3>(day, input_path, part),
+        4 => run_solution::<day4::Day4>(day, input_path, part),
+        5 => run_solution::<day5::Day5>(day, input_path, part),
+        6 => run_solution::<day6::Day6>(day, input_path, part),
+        7 => run_solution::<day7::Day7>(day, input_path, part),
+        8 => run_solution::<day8::Day8>(day, input_path, part),
+        9 => run_solution::<day9::Day9>(day, input_path, part),
+        10 => run_solution::<day10::Day10>(day, input_path, part),
+        11 => run_solution::<day11::Day11>(day, input_path, part),
+        12 => run_solution::<day12::Day12>(day, input_path, part),
+        13 => run_solution::<day13::Day13>(day, input_path, part),
+        14 => run_solution::<day14::Day14>(day, input_path, part),
+        15 => run_solution::<day15::Day15>(day, input_path, part),
+        16 => run_solution::<day16::Day16>(day, input_path, part),
+        17 => run_solution::<day17::Day17>(day, input_path, part),
+        18 => run_solution::<day18::Day18>(day, input_path, part),
+        19 => run_solution::<day19::Day19>(day, input_path, part),
+        20 => run_solution::<day20::Day20>(day, input_path, part),
+        21 => run_solution::<day21::Day21>(day, input_path, part),
+        22 => run_solution::<day22::Day22>(day, input_path, part),
+        23 => run_solution::<day23::Day23>(day, input_path, part),
+        24 => run_solution::<day24::Day24>(day, input_path, part),
+        25 => run_solution::<day25::Day25>(day, input_path, part),
+        _ => Err(RunnerError::UnknownDay(day)),
+    }
+}
+
+fn run(args: RunArguments) -> Result<(), RunnerError> {
+    match args.days {
+        DaySelection::Single(day) => {
+            let input_path = args.input.unwrap_or_else(|| default_input_path(day));
+            run_day(day, &input_path, args.part)
+        }
+        DaySelection::All => {
+            for day in 1..=NUM_DAYS {
+                println!("Day {}", day);
+                match run_day(day, &default_input_path(day), args.part) {
+                    // not every day has both parts, so just skip the missing one
+                    Ok(_) | Err(RunnerError::MissingPart { .. }) => (),
+                    Err(err) => return Err(err),
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(not(tarpaulin))]
+fn main() {
+    let args: Vec<_> = env::args().skip(1).collect();
+    if let Err(err) = parse_arguments(&args).and_then(run) {
+        eprintln!("{}", err);
+        if matches!(err, RunnerError::InvalidArguments(_)) {
+            eprintln!("{}", USAGE);
+        }
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(raw: &str) -> Vec<String> {
+        raw.split_ascii_whitespace().map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn parsing_single_day() {
+        let expected = RunArguments {
+            days: DaySelection::Single(7),
+            part: Some(2),
+            input: Some(PathBuf::from("foo/input")),
+        };
+
+        assert_eq!(
+            expected,
+            parse_arguments(&to_args("run 7 --part 2 --input foo/input")).unwrap()
+        );
+        assert_eq!(
+            expected,
+            parse_arguments(&to_args("run 7 --input foo/input --part 2")).unwrap()
+        );
+    }
+
+    #[test]
+    fn parsing_all_days() {
+        let expected = RunArguments {
+            days: DaySelection::All,
+            part: None,
+            input: None,
+        };
+
+        assert_eq!(expected, parse_arguments(&to_args("run all")).unwrap());
+    }
+
+    #[test]
+    fn parsing_fails_on_invalid_arguments() {
+        assert!(parse_arguments(&to_args("")).is_err());
+        assert!(parse_arguments(&to_args("walk 7")).is_err());
+        assert!(parse_arguments(&to_args("run")).is_err());
+        assert!(parse_arguments(&to_args("run 0")).is_err());
+        assert!(parse_arguments(&to_args("run 26")).is_err());
+        assert!(parse_arguments(&to_args("run seven")).is_err());
+        assert!(parse_arguments(&to_args("run 7 --part 3")).is_err());
+        assert!(parse_arguments(&to_args("run 7 --part")).is_err());
+        assert!(parse_arguments(&to_args("run 7 --foo bar")).is_err());
+        assert!(parse_arguments(&to_args("run all --input foo/input")).is_err());
+    }
+}
@@ -12,71 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use itertools::Itertools;
-use utils::input_read;
-
-fn part1(input: &[usize]) -> Option<usize> {
-    // if you really want to be fancy about it, you could sort the whole thing first,
-    // then be smart about choosing second value, like if v1 + v2 > 2020, don't bother
-    // checking anything above v2. But current approach works well enough
-    // and cutting edge performance is not a requirement.
-
-    for pair in input.iter().tuple_combinations::<(_, _)>() {
-        if pair.0 + pair.1 == 2020 {
-            return Some(pair.0 * pair.1);
-        }
-    }
-
-    None
-}
-
-fn part2(input: &[usize]) -> Option<usize> {
-    for triplet in input.iter().tuple_combinations::<(_, _, _)>() {
-        if triplet.0 + triplet.1 + triplet.2 == 2020 {
-            return Some(triplet.0 * triplet.1 * triplet.2);
-        }
-    }
-
-    None
-}
+use day1::Day1;
+use std::num::ParseIntError;
+use utils::input_read::InputError;
+use utils::solution::Solution;
 
 #[cfg(not(tarpaulin))]
-fn main() {
-    let input = input_read::read_line_input("input").expect("failed to read input file");
-    let part1_result = part1(&input).expect("failed to solve part1");
+fn main() -> Result<(), InputError<ParseIntError>> {
+    let input = Day1::parse_input("input")?;
+
+    let part1_result = Day1::part1(&input).expect("failed to solve part1");
     println!("Part 1 result is {}", part1_result);
 
-    let part2_result = part2(&input).expect("failed to solve part2");
+    let part2_result = Day1::part2(&input).expect("failed to solve part2");
     println!("Part 2 result is {}", part2_result);
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn part1_sample_input() {
-        let input = vec![1721, 979, 366, 299, 675, 1456];
-        let expected = 514579;
-
-        assert_eq!(expected, part1(&input).unwrap())
-    }
-
-    #[test]
-    fn part1_fails_on_invalid_input() {
-        assert!(part1(&[1, 2, 3]).is_none())
-    }
-
-    #[test]
-    fn part2_sample_input() {
-        let input = vec![1721, 979, 366, 299, 675, 1456];
-        let expected = 241861950;
-
-        assert_eq!(expected, part2(&input).unwrap())
-    }
 
-    #[test]
-    fn part2_fails_on_invalid_input() {
-        assert!(part2(&[1, 2, 3]).is_none())
-    }
+    Ok(())
 }
@@ -0,0 +1,359 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Note: I've started using 'from' rather than 'try_from' as I'm making assumption that
+// provided inputs must not be malformed.
+
+use day17::automaton::{BoundedGrid, DenseAutomaton, FiniteTopology, LifeRule, Topology};
+use std::convert::Infallible;
+use std::fmt::{self, Display, Formatter};
+use std::iter;
+use std::ops::{Index, IndexMut};
+use std::path::Path;
+use std::slice;
+use utils::input_read::{self, InputError};
+use utils::solution::Solution;
+
+const EMPTY_SEAT: char = 'L';
+const OCCUPIED_SEAT: char = '#';
+const FLOOR: char = '.';
+
+#[derive(Debug, Clone, PartialEq, Copy)]
+enum Seat {
+    Empty,
+    Occupied,
+    Floor,
+}
+
+impl From<char> for Seat {
+    fn from(value: char) -> Self {
+        match value {
+            EMPTY_SEAT => Seat::Empty,
+            OCCUPIED_SEAT => Seat::Occupied,
+            FLOOR => Seat::Floor,
+            v => panic!("invalid seat state {}", v),
+        }
+    }
+}
+
+impl From<Seat> for char {
+    fn from(seat: Seat) -> Self {
+        match seat {
+            Seat::Empty => EMPTY_SEAT,
+            Seat::Occupied => OCCUPIED_SEAT,
+            Seat::Floor => FLOOR,
+        }
+    }
+}
+
+impl Seat {
+    fn is_floor(&self) -> bool {
+        matches!(self, Seat::Floor)
+    }
+
+    fn is_occupied(&self) -> bool {
+        matches!(self, Seat::Occupied)
+    }
+}
+
+type SeatRow = Vec<Seat>;
+type SeatPosition = (usize, usize);
+
+#[derive(PartialEq)]
+struct SeatGrid {
+    rows: Vec<SeatRow>,
+}
+
+impl Index<SeatPosition> for SeatGrid {
+    type Output = Seat;
+
+    fn index(&self, index: SeatPosition) -> &Self::Output {
+        &self.rows[index.1][index.0]
+    }
+}
+
+impl IndexMut<SeatPosition> for SeatGrid {
+    fn index_mut(&mut self, index: SeatPosition) -> &mut Self::Output {
+        &mut self.rows[index.1][index.0]
+    }
+}
+
+impl Display for SeatGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.rows.iter() {
+            let row_string: String = row
+                .iter()
+                .map(|&seat| {
+                    let char: char = seat.into();
+                    char
+                })
+                .collect();
+            writeln!(f, "{}", row_string)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&[String]> for SeatGrid {
+    fn from(raw_rows: &[String]) -> Self {
+        Self {
+            rows: raw_rows
+                .iter()
+                .map(|row| row.chars().map(Seat::from).collect())
+                .collect(),
+        }
+    }
+}
+
+impl SeatGrid {
+    fn width(&self) -> usize {
+        self.rows[0].len()
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn positions(&self) -> impl Iterator<Item = SeatPosition> + '_ {
+        let width = self.width();
+        (0..self.height()).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    fn occupied_positions(&self) -> impl Iterator<Item = SeatPosition> + '_ {
+        self.positions()
+            .filter(move |&position| self[position].is_occupied())
+    }
+
+    // floor is never occupied nor affects any seat, so it is not part of the seating area at all
+    fn seating_area(&self) -> BoundedGrid {
+        BoundedGrid::with_holes(
+            self.width(),
+            self.height(),
+            self.positions()
+                .filter(|&position| self[position].is_floor()),
+        )
+        .expect("all positions are within the layout")
+    }
+
+    fn first_visible_seat(
+        &self,
+        position: SeatPosition,
+        direction: (isize, isize),
+    ) -> Option<SeatPosition> {
+        let mut translation = direction;
+        loop {
+            let translated = self.attempt_translation(position, translation)?;
+            if !self[translated].is_floor() {
+                return Some(translated);
+            }
+            translation.0 += direction.0;
+            translation.1 += direction.1;
+        }
+    }
+
+    fn attempt_translation(
+        &self,
+        position: SeatPosition,
+        translation: (isize, isize),
+    ) -> Option<SeatPosition> {
+        let (x, y) = position;
+        let (dx, dy) = translation;
+        let translated = (x as isize + dx, y as isize + dy);
+
+        if translated.0 < 0
+            || translated.0 >= self.width() as isize
+            || translated.1 < 0
+            || translated.1 >= self.height() as isize
+        {
+            None
+        } else {
+            // based on previous checks we know we can safely cast it
+            Some((translated.0 as usize, translated.1 as usize))
+        }
+    }
+}
+
+// topology in which seats are adjacent if they can see each other,
+// i.e. there's nothing but floor between them
+struct VisibleSeats {
+    seating_area: BoundedGrid,
+    // visible seats of each seat in the seating area
+    visible: Vec<Vec<SeatPosition>>,
+}
+
+impl From<&SeatGrid> for VisibleSeats {
+    fn from(grid: &SeatGrid) -> Self {
+        let seating_area = grid.seating_area();
+        let visible = (0..seating_area.num_cells())
+            .map(|index| {
+                let seat = seating_area.cell(index);
+                let mut visible = Vec::new();
+                for i in -1..=1 {
+                    for j in -1..=1 {
+                        if i == 0 && j == 0 {
+                            continue;
+                        }
+                        if let Some(visible_seat) = grid.first_visible_seat(seat, (i, j)) {
+                            visible.push(visible_seat)
+                        }
+                    }
+                }
+                visible
+            })
+            .collect();
+
+        VisibleSeats {
+            seating_area,
+            visible,
+        }
+    }
+}
+
+impl Topology for VisibleSeats {
+    type Cell = SeatPosition;
+    type Neighbours<'a> = iter::Copied<slice::Iter<'a, SeatPosition>>;
+
+    fn neighbours<'a>(&'a self, cell: &SeatPosition) -> Self::Neighbours<'a> {
+        let index = self.seating_area.index(cell).expect("not a seat");
+        self.visible[index].iter().copied()
+    }
+}
+
+impl FiniteTopology for VisibleSeats {
+    fn num_cells(&self) -> usize {
+        self.seating_area.num_cells()
+    }
+
+    fn cell(&self, index: usize) -> SeatPosition {
+        self.seating_area.cell(index)
+    }
+
+    fn index(&self, cell: &SeatPosition) -> Option<usize> {
+        self.seating_area.index(cell)
+    }
+}
+
+// runs the seating simulation until nothing changes and returns the number of occupied seats
+fn stabilise<T>(grid: &SeatGrid, adjacent_seats: T, tolerance: usize) -> usize
+where
+    T: FiniteTopology<Cell = SeatPosition>,
+{
+    // If a seat is empty (L) and there are no occupied seats adjacent to it, the seat becomes occupied.
+    // If a seat is occupied (#) and `tolerance` or more seats adjacent to it are also occupied, the seat becomes empty.
+    let survival: Vec<_> = (0..tolerance).collect();
+    let rule = LifeRule::new(&[0], &survival);
+
+    let mut seats = DenseAutomaton::new(adjacent_seats, rule, grid.occupied_positions());
+    while seats.step() {}
+    seats.alive_count()
+}
+
+pub fn part1(input: &[String]) -> usize {
+    let grid = SeatGrid::from(input);
+    stabilise(&grid, grid.seating_area(), 4)
+}
+
+pub fn part2(input: &[String]) -> usize {
+    let grid = SeatGrid::from(input);
+    // it now takes five or more visible occupied seats for an occupied seat to become empty (rather than four or more from the previous rules)
+    stabilise(&grid, VisibleSeats::from(&grid), 5)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<String>;
+    type ParseError = Infallible;
+    type Part1Output = usize;
+    type Part2Output = usize;
+
+    fn parse_input<P: AsRef<Path>>(path: P) -> Result<Self::Input, InputError<Self::ParseError>> {
+        input_read::read_line_input(path)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Part1Output> {
+        Some(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2Output> {
+        Some(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_sample_input() {
+        let input = vec![
+            "L.LL.LL.LL".to_string(),
+            "LLLLLLL.LL".to_string(),
+            "L.L.L..L..".to_string(),
+            "LLLL.LL.LL".to_string(),
+            "L.LL.LL.LL".to_string(),
+            "L.LLLLL.LL".to_string(),
+            "..L.L.....".to_string(),
+            "LLLLLLLLLL".to_string(),
+            "L.LLLLLL.L".to_string(),
+            "L.LLLLL.LL".to_string(),
+        ];
+
+        let expected = 37;
+
+        assert_eq!(expected, part1(&input))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = vec![
+            "L.LL.LL.LL".to_string(),
+            "LLLLLLL.LL".to_string(),
+            "L.L.L..L..".to_string(),
+            "LLLL.LL.LL".to_string(),
+            "L.LL.LL.LL".to_string(),
+            "L.LLLLL.LL".to_string(),
+            "..L.L.....".to_string(),
+            "LLLLLLLLLL".to_string(),
+            "L.LLLLLL.L".to_string(),
+            "L.LLLLL.LL".to_string(),
+        ];
+
+        let expected = 26;
+
+        assert_eq!(expected, part2(&input))
+    }
+
+    #[test]
+    fn display_works_as_expected() {
+        let input = vec![
+            "L.LL.LL.LL".to_string(),
+            "LLLLLLL.LL".to_string(),
+            "L.L.L..L..".to_string(),
+            "LLLL.LL.LL".to_string(),
+            "L.LL.LL.LL".to_string(),
+            "L.LLLLL.LL".to_string(),
+            "..L.L.....".to_string(),
+            "LLLLLLLLLL".to_string(),
+            "L.LLLLLL.L".to_string(),
+            "L.LLLLL.LL".to_string(),
+        ];
+        let grid = SeatGrid::from(&*input);
+
+        let mut expected = input.join("\n");
+        expected.push('\n');
+
+        assert_eq!(expected, format!("{}", grid));
+    }
+}
@@ -0,0 +1,313 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+
+const NORTH_DIRECTION: char = 'N';
+const SOUTH_DIRECTION: char = 'S';
+const EAST_DIRECTION: char = 'E';
+const WEST_DIRECTION: char = 'W';
+const LEFT_DIRECTION: char = 'L';
+const RIGHT_DIRECTION: char = 'R';
+const FORWARD_DIRECTION: char = 'F';
+
+pub(crate) const RIGHT_ANGLE: isize = 90;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionErrorKind {
+    EmptyAction,
+    UnknownDirection(char),
+    InvalidMagnitude(String),
+    /// Rotations have to be multiples of the right angle.
+    InvalidRotation(isize),
+}
+
+impl Display for ActionErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionErrorKind::EmptyAction => write!(f, "empty action"),
+            ActionErrorKind::UnknownDirection(c) => write!(f, "unknown direction '{}'", c),
+            ActionErrorKind::InvalidMagnitude(raw) => write!(f, "invalid magnitude '{}'", raw),
+            ActionErrorKind::InvalidRotation(angle) => {
+                write!(f, "rotation by {} degrees is not a right angle", angle)
+            }
+        }
+    }
+}
+
+/// Reason an action couldn't be parsed, alongside its location in the navigation log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionError {
+    // 1-based, a lone action is always on the first line
+    pub line: usize,
+    // 1-based, in characters
+    pub column: usize,
+    pub kind: ActionErrorKind,
+}
+
+impl ActionError {
+    pub(crate) fn new(column: usize, kind: ActionErrorKind) -> Self {
+        ActionError {
+            line: 1,
+            column,
+            kind,
+        }
+    }
+
+    fn on_line(self, line: usize) -> Self {
+        ActionError { line, ..self }
+    }
+}
+
+impl Display for ActionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+    Left,
+    Right,
+    Forward,
+}
+
+// as per specs, actions are "single-character"
+impl TryFrom<char> for Direction {
+    type Error = ActionErrorKind;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            NORTH_DIRECTION => Ok(Direction::North),
+            SOUTH_DIRECTION => Ok(Direction::South),
+            EAST_DIRECTION => Ok(Direction::East),
+            WEST_DIRECTION => Ok(Direction::West),
+            LEFT_DIRECTION => Ok(Direction::Left),
+            RIGHT_DIRECTION => Ok(Direction::Right),
+            FORWARD_DIRECTION => Ok(Direction::Forward),
+            v => Err(ActionErrorKind::UnknownDirection(v)),
+        }
+    }
+}
+
+impl Direction {
+    pub fn is_rotation(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    pub fn is_translation(self) -> bool {
+        matches!(
+            self,
+            Direction::North | Direction::East | Direction::South | Direction::West
+        )
+    }
+}
+
+/// Single navigation action, with rotations always being whole numbers of right angles.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Action {
+    direction: Direction,
+    magnitude: isize,
+}
+
+// splits the action into its direction and the magnitude parsed with the provided parser
+pub(crate) fn parse_action<T, F>(
+    raw: &str,
+    parse_magnitude: F,
+) -> Result<(Direction, T), ActionError>
+where
+    F: FnOnce(&str) -> Option<T>,
+{
+    let mut chars = raw.chars();
+    let raw_direction = chars
+        .next()
+        .ok_or_else(|| ActionError::new(1, ActionErrorKind::EmptyAction))?;
+    let direction = Direction::try_from(raw_direction).map_err(|kind| ActionError::new(1, kind))?;
+
+    let raw_magnitude = chars.as_str();
+    // explicit signs are not part of the format
+    let magnitude = if raw_magnitude.starts_with(|c: char| c.is_ascii_digit()) {
+        parse_magnitude(raw_magnitude)
+    } else {
+        None
+    };
+
+    match magnitude {
+        Some(magnitude) => Ok((direction, magnitude)),
+        None => Err(ActionError::new(
+            2,
+            ActionErrorKind::InvalidMagnitude(raw_magnitude.to_owned()),
+        )),
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Action {
+    type Error = ActionError;
+
+    fn try_from(raw: &'a str) -> Result<Self, Self::Error> {
+        let (direction, magnitude) = parse_action(raw, |raw| raw.parse::<isize>().ok())?;
+        Action::new(direction, magnitude).map_err(|kind| ActionError::new(2, kind))
+    }
+}
+
+impl<'a> TryFrom<&'a String> for Action {
+    type Error = ActionError;
+
+    fn try_from(raw: &'a String) -> Result<Self, Self::Error> {
+        Self::try_from(raw.as_str())
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {}", self.direction, self.magnitude)
+    }
+}
+
+impl Action {
+    pub fn new(direction: Direction, magnitude: isize) -> Result<Self, ActionErrorKind> {
+        if direction.is_rotation() && magnitude % RIGHT_ANGLE != 0 {
+            return Err(ActionErrorKind::InvalidRotation(magnitude));
+        }
+
+        Ok(Action {
+            direction,
+            magnitude,
+        })
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn magnitude(&self) -> isize {
+        self.magnitude
+    }
+
+    pub fn is_rotation(&self) -> bool {
+        self.direction.is_rotation()
+    }
+
+    pub fn is_translation(&self) -> bool {
+        self.direction.is_translation()
+    }
+}
+
+/// Actions recovered from a navigation log, with the lines that couldn't be parsed set aside.
+#[derive(Debug)]
+pub struct NavigationLog<A = Action> {
+    pub actions: Vec<A>,
+    pub errors: Vec<ActionError>,
+}
+
+impl<A> NavigationLog<A>
+where
+    A: for<'a> TryFrom<&'a str, Error = ActionError>,
+{
+    pub fn parse<S: AsRef<str>>(lines: &[S]) -> Self {
+        let mut log = NavigationLog {
+            actions: Vec::new(),
+            errors: Vec::new(),
+        };
+        for (i, line) in lines.iter().enumerate() {
+            match A::try_from(line.as_ref()) {
+                Ok(action) => log.actions.push(action),
+                Err(err) => log.errors.push(err.on_line(i + 1)),
+            }
+        }
+        log
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_parsing() {
+        let action = Action::try_from("F10").unwrap();
+        assert_eq!(
+            (Direction::Forward, 10),
+            (action.direction(), action.magnitude())
+        );
+        assert_eq!(
+            Action::new(Direction::Left, 450),
+            Action::try_from("L450").map_err(|err| err.kind)
+        );
+
+        let error = |column, kind| Err(ActionError::new(column, kind));
+        assert_eq!(error(1, ActionErrorKind::EmptyAction), Action::try_from(""));
+        assert_eq!(
+            error(1, ActionErrorKind::UnknownDirection('X')),
+            Action::try_from("X10")
+        );
+        assert_eq!(
+            error(1, ActionErrorKind::UnknownDirection('€')),
+            Action::try_from("€10")
+        );
+        for raw_magnitude in ["", "1a", "-5", "+5", " 5"].iter() {
+            assert_eq!(
+                error(
+                    2,
+                    ActionErrorKind::InvalidMagnitude(raw_magnitude.to_string())
+                ),
+                Action::try_from(&*format!("N{}", raw_magnitude))
+            );
+        }
+        assert_eq!(
+            error(2, ActionErrorKind::InvalidRotation(45)),
+            Action::try_from("R45")
+        );
+    }
+
+    #[test]
+    fn rotations_are_validated_on_construction() {
+        assert_eq!(
+            Err(ActionErrorKind::InvalidRotation(45)),
+            Action::new(Direction::Left, 45)
+        );
+        assert_eq!(
+            Err(ActionErrorKind::InvalidRotation(-30)),
+            Action::new(Direction::Right, -30)
+        );
+        assert!(Action::new(Direction::Right, -270).is_ok());
+        assert!(Action::new(Direction::North, 45).is_ok());
+    }
+
+    #[test]
+    fn bad_lines_are_set_aside() {
+        let log: NavigationLog = NavigationLog::parse(&["F10", "N3", "Q7", "R91", "F11"]);
+
+        assert_eq!(3, log.actions.len());
+        assert_eq!(
+            vec![
+                "line 3, column 1: unknown direction 'Q'".to_string(),
+                "line 4, column 2: rotation by 91 degrees is not a right angle".to_string(),
+            ],
+            log.errors
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,251 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::action::{parse_action, ActionError, ActionErrorKind, RIGHT_ANGLE};
+use crate::{Action, Direction, NavigationMode, Ship};
+use std::convert::TryFrom;
+
+type ContinuousPosition = (f64, f64);
+
+/// Action with a fractional magnitude. Rotations can be by any angle, in degrees.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ContinuousAction {
+    direction: Direction,
+    magnitude: f64,
+}
+
+impl<'a> TryFrom<&'a str> for ContinuousAction {
+    type Error = ActionError;
+
+    fn try_from(raw: &'a str) -> Result<Self, Self::Error> {
+        let (direction, magnitude) = parse_action(raw, |raw| {
+            raw.parse::<f64>()
+                .ok()
+                .filter(|magnitude| magnitude.is_finite())
+        })?;
+        ContinuousAction::new(direction, magnitude).map_err(|kind| ActionError::new(2, kind))
+    }
+}
+
+impl ContinuousAction {
+    /// The magnitude has to be finite and not negative.
+    pub fn new(direction: Direction, magnitude: f64) -> Result<Self, ActionErrorKind> {
+        if !magnitude.is_finite() || magnitude < 0.0 {
+            return Err(ActionErrorKind::InvalidMagnitude(magnitude.to_string()));
+        }
+
+        Ok(ContinuousAction {
+            direction,
+            magnitude,
+        })
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn magnitude(&self) -> f64 {
+        self.magnitude
+    }
+
+    // the same action in the integer model, if it can be represented there exactly
+    fn to_exact(self) -> Option<Action> {
+        // `isize::MAX` rounds up to 2^63, which itself is out of range already
+        if self.magnitude.fract() != 0.0 || self.magnitude.abs() >= isize::MAX as f64 {
+            return None;
+        }
+
+        Action::new(self.direction, self.magnitude as isize).ok()
+    }
+}
+
+// rotates the point clockwise around the origin
+fn rotate((x, y): ContinuousPosition, degrees: f64) -> ContinuousPosition {
+    // right angles are kept exact rather than picking up the rounding errors of sin and cos
+    if degrees % RIGHT_ANGLE as f64 == 0.0 {
+        return match (degrees / RIGHT_ANGLE as f64).rem_euclid(4.0) as u8 {
+            0 => (x, y),
+            1 => (y, -x),
+            2 => (-x, -y),
+            _ => (-y, x),
+        };
+    }
+
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    (x * cos + y * sin, y * cos - x * sin)
+}
+
+fn translation(direction: Direction, magnitude: f64) -> ContinuousPosition {
+    match direction {
+        Direction::North => (0.0, magnitude),
+        Direction::South => (0.0, -magnitude),
+        Direction::East => (magnitude, 0.0),
+        Direction::West => (-magnitude, 0.0),
+        _ => unreachable!(),
+    }
+}
+
+struct ContinuousShip {
+    position: ContinuousPosition,
+    waypoint: ContinuousPosition,
+    mode: NavigationMode,
+}
+
+impl ContinuousShip {
+    fn apply_action(&mut self, action: ContinuousAction) {
+        let (x, y) = self.position;
+        match action.direction {
+            Direction::Right => self.waypoint = rotate(self.waypoint, action.magnitude),
+            Direction::Left => self.waypoint = rotate(self.waypoint, -action.magnitude),
+            Direction::Forward => {
+                let (dx, dy) = self.waypoint;
+                self.position = (x + dx * action.magnitude, y + dy * action.magnitude)
+            }
+            direction => {
+                let (dx, dy) = translation(direction, action.magnitude);
+                if self.mode == NavigationMode::Waypoint {
+                    let (wx, wy) = self.waypoint;
+                    self.waypoint = (wx + dx, wy + dy)
+                } else {
+                    self.position = (x + dx, y + dy)
+                }
+            }
+        }
+    }
+}
+
+/// Final position of the ship after following the actions. If every action is expressible
+/// with whole numbers and right angles, the exact integer model is used instead,
+/// as long as the ship doesn't get too far away for it. Its result is still returned as floats,
+/// so coordinates beyond 2^53 are rounded to the nearest representable value.
+pub fn navigate(actions: &[ContinuousAction], mode: NavigationMode) -> ContinuousPosition {
+    let (wx, wy) = mode.initial_waypoint();
+
+    if let Some(exact) = actions
+        .iter()
+        .map(|action| action.to_exact())
+        .collect::<Option<Vec<_>>>()
+    {
+        // the ship might get too far for the integers, in which case it's left to the floats
+        let mut ship = Ship::new((wx, wy), mode);
+        if exact
+            .into_iter()
+            .try_for_each(|action| ship.apply_action(action))
+            .is_some()
+        {
+            return (ship.position.0 as f64, ship.position.1 as f64);
+        }
+    }
+
+    let mut ship = ContinuousShip {
+        position: (0.0, 0.0),
+        waypoint: (wx as f64, wy as f64),
+        mode,
+    };
+    actions.iter().for_each(|&action| ship.apply_action(action));
+    ship.position
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NavigationLog;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn assert_close(expected: ContinuousPosition, actual: ContinuousPosition) {
+        assert!(
+            (expected.0 - actual.0).abs() < EPSILON && (expected.1 - actual.1).abs() < EPSILON,
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        )
+    }
+
+    fn parse(lines: &[&str]) -> Vec<ContinuousAction> {
+        let log: NavigationLog<ContinuousAction> = NavigationLog::parse(lines);
+        assert!(log.errors.is_empty());
+        log.actions
+    }
+
+    #[test]
+    fn fractional_actions_parsing() {
+        let action = ContinuousAction::try_from("L22.5").unwrap();
+        assert_eq!(Direction::Left, action.direction());
+        assert_eq!(22.5, action.magnitude());
+        assert!(ContinuousAction::try_from("F-1.5").is_err());
+        assert!(ContinuousAction::try_from("Finf").is_err());
+        assert!(ContinuousAction::try_from("F1e400").is_err());
+        assert!(ContinuousAction::try_from("F.").is_err());
+    }
+
+    #[test]
+    fn magnitudes_are_validated() {
+        assert!(ContinuousAction::new(Direction::Forward, 0.0).is_ok());
+        for magnitude in [-1.5, f64::NAN, f64::INFINITY, f64::NEG_INFINITY].iter() {
+            assert_eq!(
+                Err(ActionErrorKind::InvalidMagnitude(magnitude.to_string())),
+                ContinuousAction::new(Direction::Forward, *magnitude)
+            );
+        }
+    }
+
+    #[test]
+    fn right_angles_use_exact_model() {
+        let actions = parse(&["F10", "N3", "F7", "R90", "F11"]);
+        assert!(actions.iter().all(|action| action.to_exact().is_some()));
+
+        assert_eq!((17.0, -8.0), navigate(&actions, NavigationMode::Absolute));
+        assert_eq!((214.0, -72.0), navigate(&actions, NavigationMode::Waypoint));
+    }
+
+    #[test]
+    fn huge_magnitudes_fall_back_to_floats() {
+        let actions = parse(&["F9223372036854775808"]);
+        assert!(actions[0].to_exact().is_none());
+        assert_eq!(
+            (2f64.powi(63), 0.0),
+            navigate(&actions, NavigationMode::Absolute)
+        );
+
+        let actions = parse(&["F1e18", "R90"]);
+        assert!(actions.iter().all(|action| action.to_exact().is_some()));
+        assert_eq!((1e19, 1e18), navigate(&actions, NavigationMode::Waypoint));
+        assert_eq!((1e18, 0.0), navigate(&actions, NavigationMode::Absolute));
+    }
+
+    #[test]
+    fn arbitrary_angles_and_fractions() {
+        let half_diagonal = 0.5_f64.sqrt();
+
+        let actions = parse(&["L45", "F2", "R135", "F0.5"]);
+        assert!(actions[0].to_exact().is_none());
+        assert_close(
+            (2.0 * half_diagonal, 2.0 * half_diagonal - 0.5),
+            navigate(&actions, NavigationMode::Absolute),
+        );
+
+        // the waypoint starts at (10, 1) and turns around the ship
+        let actions = parse(&["R30", "F1", "N0.5"]);
+        let (sin, cos) = 30_f64.to_radians().sin_cos();
+        assert_close(
+            (10.0 * cos + sin, cos - 10.0 * sin),
+            navigate(&actions, NavigationMode::Waypoint),
+        );
+
+        // right angles within an otherwise continuous log don't lose precision
+        let actions = parse(&["R360", "L90", "F1.5", "R270", "F2"]);
+        assert_eq!((-2.0, 1.5), navigate(&actions, NavigationMode::Absolute));
+    }
+}
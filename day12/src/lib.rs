@@ -0,0 +1,302 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::Infallible;
+use std::path::Path;
+use utils::input_read::{self, InputError};
+use utils::solution::Solution;
+
+pub use action::{Action, ActionError, ActionErrorKind, Direction, NavigationLog};
+pub use continuous::{navigate, ContinuousAction};
+
+use action::RIGHT_ANGLE;
+
+mod action;
+mod continuous;
+
+type Position = (isize, isize);
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum NavigationMode {
+    Absolute,
+    Waypoint,
+}
+
+impl NavigationMode {
+    fn initial_waypoint(self) -> Position {
+        match self {
+            // in absolute mode the waypoint is just the heading of the ship
+            NavigationMode::Absolute => (1, 0),
+            NavigationMode::Waypoint => (10, 1),
+        }
+    }
+}
+
+struct Ship {
+    position: Position,
+    waypoint: Waypoint,
+    mode: NavigationMode,
+}
+
+impl Ship {
+    fn new(waypoint_position: Position, mode: NavigationMode) -> Self {
+        Ship {
+            position: (0, 0),
+            waypoint: Waypoint {
+                relative_position: waypoint_position,
+            },
+            mode,
+        }
+    }
+
+    // `None` if the ship ends up too far away to keep track of
+    fn apply_action(&mut self, action: Action) -> Option<()> {
+        if action.is_rotation() {
+            self.waypoint.apply_rotation(action)
+        } else if action.is_translation() {
+            if self.mode == NavigationMode::Waypoint {
+                self.waypoint.apply_translation(action)
+            } else {
+                self.apply_self_translation(action)
+            }
+        } else {
+            // it must be forward
+            self.move_towards_waypoint(action)
+        }
+    }
+
+    fn apply_self_translation(&mut self, action: Action) -> Option<()> {
+        self.position = translate(self.position, action)?;
+        Some(())
+    }
+
+    fn move_towards_waypoint(&mut self, action: Action) -> Option<()> {
+        debug_assert_eq!(action.direction(), Direction::Forward);
+        let (x, y) = self.position;
+        let dx = self
+            .waypoint
+            .relative_position
+            .0
+            .checked_mul(action.magnitude())?;
+        let dy = self
+            .waypoint
+            .relative_position
+            .1
+            .checked_mul(action.magnitude())?;
+        self.position = (x.checked_add(dx)?, y.checked_add(dy)?);
+        Some(())
+    }
+
+    fn manhattan_distance(&self) -> Option<usize> {
+        let (x, y) = self.position;
+        x.unsigned_abs().checked_add(y.unsigned_abs())
+    }
+}
+
+fn translate((x, y): Position, action: Action) -> Option<Position> {
+    let magnitude = action.magnitude();
+    match action.direction() {
+        Direction::North => Some((x, y.checked_add(magnitude)?)),
+        Direction::South => Some((x, y.checked_sub(magnitude)?)),
+        Direction::East => Some((x.checked_add(magnitude)?, y)),
+        Direction::West => Some((x.checked_sub(magnitude)?, y)),
+        _ => unreachable!(),
+    }
+}
+
+struct Waypoint {
+    relative_position: Position,
+}
+
+impl Waypoint {
+    fn apply_rotation(&mut self, action: Action) -> Option<()> {
+        // actions can only ever rotate by whole numbers of right angles
+        let quarter_turns = action.magnitude() / RIGHT_ANGLE;
+        let clockwise_turns = if action.direction() == Direction::Right {
+            quarter_turns
+        } else {
+            -quarter_turns
+        };
+
+        let (x, y) = self.relative_position;
+        match clockwise_turns.rem_euclid(4) {
+            0 => (),
+            1 => self.relative_position = (y, x.checked_neg()?),
+            2 => self.relative_position = (x.checked_neg()?, y.checked_neg()?),
+            3 => self.relative_position = (y.checked_neg()?, x),
+            _ => unreachable!(),
+        }
+        Some(())
+    }
+
+    fn apply_translation(&mut self, action: Action) -> Option<()> {
+        debug_assert!(action.is_translation());
+        self.relative_position = translate(self.relative_position, action)?;
+        Some(())
+    }
+}
+
+pub fn part1(actions: &[Action]) -> Option<usize> {
+    let mode = NavigationMode::Absolute;
+    let mut ship = Ship::new(mode.initial_waypoint(), mode);
+
+    actions
+        .iter()
+        .try_for_each(|&action| ship.apply_action(action))?;
+
+    ship.manhattan_distance()
+}
+
+pub fn part2(actions: &[Action]) -> Option<usize> {
+    let mode = NavigationMode::Waypoint;
+    let mut ship = Ship::new(mode.initial_waypoint(), mode);
+
+    actions
+        .iter()
+        .try_for_each(|&action| ship.apply_action(action))?;
+
+    ship.manhattan_distance()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = NavigationLog;
+    type ParseError = Infallible;
+    type Part1Output = usize;
+    type Part2Output = usize;
+
+    fn parse_input<P: AsRef<Path>>(path: P) -> Result<Self::Input, InputError<Self::ParseError>> {
+        let lines: Vec<String> = input_read::read_line_input(path)?;
+        // the malformed lines are set aside rather than failing the whole log
+        Ok(NavigationLog::parse(&lines))
+    }
+
+    fn input_warnings(input: &Self::Input) -> Vec<String> {
+        input
+            .errors
+            .iter()
+            .map(|err| format!("skipping malformed action - {}", err))
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Part1Output> {
+        part1(&input.actions)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2Output> {
+        part2(&input.actions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_sample_input() {
+        let input = vec![
+            "F10".to_string(),
+            "N3".to_string(),
+            "F7".to_string(),
+            "R90".to_string(),
+            "F11".to_string(),
+        ];
+
+        let expected = 25;
+
+        assert_eq!(Some(expected), part1(&NavigationLog::parse(&input).actions));
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = vec![
+            "F10".to_string(),
+            "N3".to_string(),
+            "F7".to_string(),
+            "R90".to_string(),
+            "F11".to_string(),
+        ];
+
+        let expected = 286;
+
+        assert_eq!(Some(expected), part2(&NavigationLog::parse(&input).actions));
+    }
+
+    #[test]
+    fn malformed_lines_are_skipped() {
+        let input = vec![
+            "F10".to_string(),
+            "N3".to_string(),
+            "F7".to_string(),
+            "X5".to_string(),
+            "R90".to_string(),
+            "L45".to_string(),
+            "F11".to_string(),
+        ];
+
+        let log = NavigationLog::parse(&input);
+        assert_eq!(
+            vec![4, 6],
+            log.errors.iter().map(|err| err.line).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(25), part1(&log.actions));
+        assert_eq!(Some(286), part2(&log.actions));
+        assert_eq!(
+            vec![
+                "skipping malformed action - line 4, column 1: unknown direction 'X'",
+                "skipping malformed action - line 6, column 2: \
+                 rotation by 45 degrees is not a right angle"
+            ],
+            Day12::input_warnings(&log)
+        );
+    }
+
+    #[test]
+    fn overflows_are_reported() {
+        let actions = NavigationLog::parse(&["F9223372036854775807", "F1"]).actions;
+        assert_eq!(None, part1(&actions));
+        assert_eq!(None, part2(&actions[..1]));
+
+        let actions = NavigationLog::parse(&["S9223372036854775807", "S1"]).actions;
+        assert_eq!(Some(isize::MAX as usize), part1(&actions[..1]));
+        assert_eq!(Some(isize::MAX as usize + 1), part1(&actions));
+        assert_eq!(None, part1(&[actions[0], actions[0], actions[1]]));
+    }
+
+    #[test]
+    fn rotations_wrap_around() {
+        let mut waypoint = Waypoint {
+            relative_position: (10, 4),
+        };
+        let rotate = |direction, magnitude| Action::new(direction, magnitude).unwrap();
+
+        waypoint
+            .apply_rotation(rotate(Direction::Right, 450))
+            .unwrap();
+        assert_eq!((4, -10), waypoint.relative_position);
+        waypoint
+            .apply_rotation(rotate(Direction::Left, 180))
+            .unwrap();
+        assert_eq!((-4, 10), waypoint.relative_position);
+        waypoint
+            .apply_rotation(rotate(Direction::Left, 720))
+            .unwrap();
+        assert_eq!((-4, 10), waypoint.relative_position);
+        waypoint
+            .apply_rotation(rotate(Direction::Left, 90))
+            .unwrap();
+        assert_eq!((-10, -4), waypoint.relative_position);
+    }
+}
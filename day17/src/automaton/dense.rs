@@ -0,0 +1,159 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{FiniteTopology, Rule};
+use rayon::prelude::*;
+
+/// Automaton storing state of every cell of a finite topology.
+pub struct DenseAutomaton<T, R> {
+    topology: T,
+    rule: R,
+    alive: Vec<bool>,
+}
+
+impl<T: FiniteTopology, R: Rule> DenseAutomaton<T, R> {
+    /// Creates new automaton with the provided cells being initially alive. Cells that are not
+    /// part of the topology are ignored.
+    pub fn new<I>(topology: T, rule: R, alive: I) -> Self
+    where
+        I: IntoIterator<Item = T::Cell>,
+    {
+        let mut automaton = DenseAutomaton {
+            alive: vec![false; topology.num_cells()],
+            topology,
+            rule,
+        };
+        for cell in alive {
+            automaton.set_alive(&cell, true);
+        }
+        automaton
+    }
+
+    pub fn topology(&self) -> &T {
+        &self.topology
+    }
+
+    /// Changes state of the provided cell. Returns false if it is not part of the topology.
+    pub fn set_alive(&mut self, cell: &T::Cell, alive: bool) -> bool {
+        match self.topology.index(cell) {
+            Some(index) => {
+                self.alive[index] = alive;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_alive(&self, cell: &T::Cell) -> bool {
+        self.topology
+            .index(cell)
+            .map(|index| self.alive[index])
+            .unwrap_or_default()
+    }
+
+    pub fn alive_count(&self) -> usize {
+        self.alive.iter().filter(|&&alive| alive).count()
+    }
+
+    pub fn alive_cells(&self) -> impl Iterator<Item = T::Cell> + '_ {
+        self.alive
+            .iter()
+            .enumerate()
+            .filter(|(_, &alive)| alive)
+            .map(move |(index, _)| self.topology.cell(index))
+    }
+
+    fn next_state(&self, index: usize) -> bool {
+        let live_neighbours = self
+            .topology
+            .neighbours(&self.topology.cell(index))
+            .filter(|neighbour| self.is_alive(neighbour))
+            .count();
+
+        self.rule.next_state(self.alive[index], live_neighbours)
+    }
+
+    fn replace_generation(&mut self, next: Vec<bool>) -> bool {
+        let changed = next != self.alive;
+        self.alive = next;
+        changed
+    }
+
+    /// Advances the automaton by a single generation. Returns whether anything has changed.
+    pub fn step(&mut self) -> bool {
+        let next = (0..self.alive.len())
+            .map(|index| self.next_state(index))
+            .collect();
+
+        self.replace_generation(next)
+    }
+}
+
+impl<T, R> DenseAutomaton<T, R>
+where
+    T: FiniteTopology + Sync,
+    R: Rule + Sync,
+{
+    /// Parallel equivalent of `step`.
+    pub fn step_par(&mut self) -> bool {
+        let next = (0..self.alive.len())
+            .into_par_iter()
+            .map(|index| self.next_state(index))
+            .collect();
+
+        self.replace_generation(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::automaton::{BoundedGrid, LifeRule};
+
+    #[test]
+    fn blinker_oscillates_within_grid() {
+        let blinker = vec![(1, 0), (1, 1), (1, 2)];
+        let mut automaton = DenseAutomaton::new(
+            BoundedGrid::new(3, 3),
+            LifeRule::new(&[3], &[2, 3]),
+            blinker,
+        );
+
+        assert!(automaton.step());
+        assert_eq!(
+            vec![(0, 1), (1, 1), (2, 1)],
+            automaton.alive_cells().collect::<Vec<_>>()
+        );
+
+        assert!(automaton.step_par());
+        assert_eq!(
+            vec![(1, 0), (1, 1), (1, 2)],
+            automaton.alive_cells().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn holes_never_come_alive() {
+        let grid = BoundedGrid::with_holes(3, 1, vec![(1, 0)]).unwrap();
+        // everything without live neighbours comes to life
+        let rule = |alive: bool, live_neighbours: usize| alive || live_neighbours == 0;
+        let mut automaton = DenseAutomaton::new(grid, rule, vec![(1, 0)]);
+
+        assert_eq!(0, automaton.alive_count());
+        assert!(automaton.step());
+        assert_eq!(2, automaton.alive_count());
+        assert!(!automaton.is_alive(&(1, 0)));
+        assert!(!automaton.step());
+    }
+}
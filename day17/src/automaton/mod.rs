@@ -0,0 +1,30 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Generic engine for life-like cellular automata. The space the cells live in is described
+// by a `Topology`, the way cells are born and die by a `Rule`, and the actual state is kept
+// either sparsely (only the live cells, for unbounded spaces) or densely (every cell of
+// a finite space).
+
+pub use dense::DenseAutomaton;
+pub use rule::{LifeRule, Rule};
+pub use sparse::SparseAutomaton;
+pub use topology::{
+    BoundedGrid, FiniteTopology, HexAxial, HoleOutOfBounds, MirroredMoore, Moore, Topology,
+};
+
+mod dense;
+mod rule;
+mod sparse;
+mod topology;
@@ -0,0 +1,79 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Decides on the next state of a cell based on its current state and number of live neighbours.
+pub trait Rule {
+    fn next_state(&self, alive: bool, live_neighbours: usize) -> bool;
+}
+
+impl<F> Rule for F
+where
+    F: Fn(bool, usize) -> bool,
+{
+    fn next_state(&self, alive: bool, live_neighbours: usize) -> bool {
+        self(alive, live_neighbours)
+    }
+}
+
+/// Rule defined by the numbers of live neighbours causing a dead cell to become alive
+/// and a live cell to stay alive, i.e. Conway's game of life is B3/S23.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LifeRule {
+    birth: Vec<usize>,
+    survival: Vec<usize>,
+}
+
+impl LifeRule {
+    pub fn new(birth: &[usize], survival: &[usize]) -> Self {
+        LifeRule {
+            birth: birth.to_vec(),
+            survival: survival.to_vec(),
+        }
+    }
+}
+
+impl Rule for LifeRule {
+    fn next_state(&self, alive: bool, live_neighbours: usize) -> bool {
+        if alive {
+            self.survival.contains(&live_neighbours)
+        } else {
+            self.birth.contains(&live_neighbours)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn life_rule_follows_birth_and_survival_counts() {
+        let conway = LifeRule::new(&[3], &[2, 3]);
+
+        assert!(conway.next_state(false, 3));
+        assert!(!conway.next_state(false, 2));
+        assert!(conway.next_state(true, 2));
+        assert!(conway.next_state(true, 3));
+        assert!(!conway.next_state(true, 1));
+        assert!(!conway.next_state(true, 4));
+    }
+
+    #[test]
+    fn closures_can_be_used_as_rules() {
+        let rule = |alive: bool, live_neighbours: usize| !alive && live_neighbours == 0;
+
+        assert!(rule.next_state(false, 0));
+        assert!(!rule.next_state(true, 0));
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{Rule, Topology};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Automaton keeping track of only the live cells, so it can work with unbounded topologies.
+/// As a consequence, it can't be used with rules bringing cells without any live neighbours
+/// to life.
+pub struct SparseAutomaton<T: Topology, R> {
+    topology: T,
+    rule: R,
+    alive: HashSet<T::Cell>,
+}
+
+impl<T: Topology, R: Rule> SparseAutomaton<T, R> {
+    pub fn new<I>(topology: T, rule: R, alive: I) -> Self
+    where
+        I: IntoIterator<Item = T::Cell>,
+    {
+        assert!(
+            !rule.next_state(false, 0),
+            "sparse automaton can't use rules giving birth to isolated cells"
+        );
+
+        SparseAutomaton {
+            topology,
+            rule,
+            alive: alive.into_iter().collect(),
+        }
+    }
+
+    pub fn topology(&self) -> &T {
+        &self.topology
+    }
+
+    pub fn is_alive(&self, cell: &T::Cell) -> bool {
+        self.alive.contains(cell)
+    }
+
+    pub fn alive_count(&self) -> usize {
+        self.alive.len()
+    }
+
+    pub fn alive_cells(&self) -> impl Iterator<Item = &T::Cell> {
+        self.alive.iter()
+    }
+
+    fn count_neighbours(&self, cell: &T::Cell, counts: &mut HashMap<T::Cell, usize>) {
+        for neighbour in self.topology.neighbours(cell) {
+            *counts.entry(neighbour).or_insert(0) += 1;
+        }
+    }
+
+    fn survives_in_isolation(&self, cell: &T::Cell, counts: &HashMap<T::Cell, usize>) -> bool {
+        !counts.contains_key(cell) && self.rule.next_state(true, 0)
+    }
+
+    fn replace_generation(&mut self, next: HashSet<T::Cell>) -> bool {
+        let changed = next != self.alive;
+        self.alive = next;
+        changed
+    }
+
+    /// Advances the automaton by a single generation. Returns whether anything has changed.
+    pub fn step(&mut self) -> bool {
+        // every cell that could possibly be alive in the next generation is either alive now
+        // or has at least a single live neighbour
+        let mut counts = HashMap::new();
+        for cell in self.alive.iter() {
+            self.count_neighbours(cell, &mut counts);
+        }
+
+        let next = self
+            .alive
+            .iter()
+            .filter(|cell| self.survives_in_isolation(cell, &counts))
+            .cloned()
+            .chain(
+                counts
+                    .iter()
+                    .filter(|(cell, &count)| self.rule.next_state(self.is_alive(cell), count))
+                    .map(|(cell, _)| cell.clone()),
+            )
+            .collect();
+
+        self.replace_generation(next)
+    }
+}
+
+impl<T, R> SparseAutomaton<T, R>
+where
+    T: Topology + Sync,
+    T::Cell: Send + Sync,
+    R: Rule + Sync,
+{
+    /// Parallel equivalent of `step`.
+    pub fn step_par(&mut self) -> bool {
+        let counts = self
+            .alive
+            .par_iter()
+            .fold(HashMap::new, |mut counts, cell| {
+                self.count_neighbours(cell, &mut counts);
+                counts
+            })
+            .reduce(HashMap::new, |mut merged, counts| {
+                for (cell, count) in counts {
+                    *merged.entry(cell).or_insert(0) += count;
+                }
+                merged
+            });
+
+        let survivors = self
+            .alive
+            .par_iter()
+            .filter(|cell| self.survives_in_isolation(cell, &counts))
+            .cloned();
+
+        let next = counts
+            .par_iter()
+            .filter(|(cell, &count)| self.rule.next_state(self.is_alive(cell), count))
+            .map(|(cell, _)| cell.clone())
+            .chain(survivors)
+            .collect();
+
+        self.replace_generation(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::automaton::{LifeRule, Moore};
+    use crate::Point;
+
+    fn blinker() -> Vec<Point<2>> {
+        vec![Point([0, -1]), Point([0, 0]), Point([0, 1])]
+    }
+
+    #[test]
+    fn blinker_oscillates() {
+        let mut automaton = SparseAutomaton::new(Moore, LifeRule::new(&[3], &[2, 3]), blinker());
+
+        assert!(automaton.step());
+        assert_eq!(3, automaton.alive_count());
+        assert!(automaton.is_alive(&Point([-1, 0])));
+        assert!(automaton.is_alive(&Point([1, 0])));
+        assert!(!automaton.is_alive(&Point([0, 1])));
+
+        automaton.step();
+        let mut alive: Vec<_> = automaton.alive_cells().cloned().collect();
+        alive.sort();
+        assert_eq!(blinker(), alive);
+    }
+
+    #[test]
+    fn block_is_stable() {
+        let block = vec![Point([0, 0]), Point([0, 1]), Point([1, 0]), Point([1, 1])];
+        let mut automaton = SparseAutomaton::new(Moore, LifeRule::new(&[3], &[2, 3]), block);
+
+        assert!(!automaton.step());
+        assert!(!automaton.step_par());
+        assert_eq!(4, automaton.alive_count());
+    }
+
+    #[test]
+    fn parallel_step_matches_sequential_step() {
+        let glider = vec![
+            Point([1, 0]),
+            Point([2, 1]),
+            Point([0, 2]),
+            Point([1, 2]),
+            Point([2, 2]),
+        ];
+        let rule = LifeRule::new(&[3], &[2, 3]);
+        let mut sequential = SparseAutomaton::new(Moore, rule.clone(), glider.clone());
+        let mut parallel = SparseAutomaton::new(Moore, rule, glider);
+
+        for _ in 0..8 {
+            sequential.step();
+            parallel.step_par();
+            assert_eq!(sequential.alive, parallel.alive);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn rules_with_spontaneous_birth_are_rejected() {
+        SparseAutomaton::new(Moore, LifeRule::new(&[0], &[]), blinker());
+    }
+}
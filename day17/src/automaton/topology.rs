@@ -0,0 +1,421 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::point::{AdjacentPoints, Point};
+use std::hash::Hash;
+
+/// Describes the space the cells live in by defining which cells neighbour each other.
+pub trait Topology {
+    type Cell: Clone + Eq + Hash;
+    type Neighbours<'a>: Iterator<Item = Self::Cell>
+    where
+        Self: 'a;
+
+    fn neighbours<'a>(&'a self, cell: &Self::Cell) -> Self::Neighbours<'a>;
+}
+
+/// Topology with a finite number of cells that could be enumerated and densely indexed.
+pub trait FiniteTopology: Topology {
+    fn num_cells(&self) -> usize;
+
+    /// Returns the cell at the provided index, which must be smaller than `num_cells`.
+    fn cell(&self, index: usize) -> Self::Cell;
+
+    /// Returns index of the provided cell or `None` if it is not part of the topology.
+    fn index(&self, cell: &Self::Cell) -> Option<usize>;
+}
+
+/// Unbounded N-dimensional space where neighbours of a cell are all the cells
+/// whose coordinates differ by at most one in every dimension.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Moore<const N: usize>;
+
+impl<const N: usize> Topology for Moore<N> {
+    type Cell = Point<N>;
+    type Neighbours<'a> = AdjacentPoints<N>;
+
+    fn neighbours<'a>(&'a self, cell: &Point<N>) -> Self::Neighbours<'a> {
+        cell.adjacent_points()
+    }
+}
+
+/// Moore neighbourhood for states that are mirror-symmetric in every dimension starting from
+/// `mirrored_from`, i.e. a cell is alive if and only if the cell with any of those coordinates
+/// negated is alive as well. Only cells with non-negative mirrored coordinates are part of it,
+/// each representing all of its mirror images, and neighbours are repeated as many times
+/// as the number of mirror images of the cell they are adjacent to.
+#[derive(Debug, Clone, Copy)]
+pub struct MirroredMoore<const N: usize> {
+    mirrored_from: usize,
+}
+
+impl<const N: usize> MirroredMoore<N> {
+    pub fn new(mirrored_from: usize) -> Self {
+        assert!(mirrored_from <= N);
+        MirroredMoore { mirrored_from }
+    }
+
+    /// Number of cells in the full space the provided cell represents.
+    pub fn multiplicity(&self, cell: &Point<N>) -> usize {
+        1 << cell.0[self.mirrored_from..]
+            .iter()
+            .filter(|&&coord| coord != 0)
+            .count()
+    }
+}
+
+/// Iterator over weighted neighbours of a cell in the `MirroredMoore` topology.
+pub struct MirroredAdjacentPoints<const N: usize> {
+    origin: Point<N>,
+    mirrored_from: usize,
+    adjacent: AdjacentPoints<N>,
+    // neighbour that still has to be repeated and the number of remaining repetitions
+    pending: Option<(Point<N>, usize)>,
+}
+
+impl<const N: usize> Iterator for MirroredAdjacentPoints<N> {
+    type Item = Point<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((point, remaining)) = self.pending.take() {
+            if remaining > 1 {
+                self.pending = Some((point, remaining - 1));
+            }
+            return Some(point);
+        }
+
+        let mirrored_from = self.mirrored_from;
+        let origin = self.origin;
+        let point = self
+            .adjacent
+            .find(|point| point.0[mirrored_from..].iter().all(|&coord| coord >= 0))?;
+
+        // both the origin (at 1) and its mirror image (at -1) are adjacent to points at 0,
+        // in every other case only one of them is
+        let weight = 1
+            << (mirrored_from..N)
+                .filter(|&dim| origin.0[dim] == 1 && point.0[dim] == 0)
+                .count();
+        if weight > 1 {
+            self.pending = Some((point, weight - 1));
+        }
+
+        Some(point)
+    }
+}
+
+impl<const N: usize> Topology for MirroredMoore<N> {
+    type Cell = Point<N>;
+    type Neighbours<'a> = MirroredAdjacentPoints<N>;
+
+    fn neighbours<'a>(&'a self, cell: &Point<N>) -> Self::Neighbours<'a> {
+        MirroredAdjacentPoints {
+            origin: *cell,
+            mirrored_from: self.mirrored_from,
+            adjacent: cell.adjacent_points(),
+            pending: None,
+        }
+    }
+}
+
+/// Unbounded hexagonal tiling using axial `(q, r)` coordinates.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HexAxial;
+
+impl HexAxial {
+    pub const EAST: Point<2> = Point([1, 0]);
+    pub const NORTH_EAST: Point<2> = Point([1, -1]);
+    pub const NORTH_WEST: Point<2> = Point([0, -1]);
+    pub const WEST: Point<2> = Point([-1, 0]);
+    pub const SOUTH_WEST: Point<2> = Point([-1, 1]);
+    pub const SOUTH_EAST: Point<2> = Point([0, 1]);
+
+    const DIRECTIONS: [Point<2>; 6] = [
+        Self::EAST,
+        Self::NORTH_EAST,
+        Self::NORTH_WEST,
+        Self::WEST,
+        Self::SOUTH_WEST,
+        Self::SOUTH_EAST,
+    ];
+}
+
+/// Iterator over the six hexes surrounding the origin.
+pub struct HexNeighbours {
+    origin: Point<2>,
+    direction: usize,
+}
+
+impl Iterator for HexNeighbours {
+    type Item = Point<2>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let direction = HexAxial::DIRECTIONS.get(self.direction)?;
+        self.direction += 1;
+        Some(self.origin + *direction)
+    }
+}
+
+impl Topology for HexAxial {
+    type Cell = Point<2>;
+    type Neighbours<'a> = HexNeighbours;
+
+    fn neighbours<'a>(&'a self, cell: &Point<2>) -> Self::Neighbours<'a> {
+        HexNeighbours {
+            origin: *cell,
+            direction: 0,
+        }
+    }
+}
+
+type GridCell = (usize, usize);
+
+/// Error returned when a hole of a `BoundedGrid` lies outside of it.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct HoleOutOfBounds {
+    pub hole: GridCell,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Finite two dimensional grid where neighbours of a cell are the (up to) eight cells
+/// surrounding it. Some of the cells might be holes, which are not part of the grid at all.
+#[derive(Debug, Clone)]
+pub struct BoundedGrid {
+    width: usize,
+    height: usize,
+    // index of each position in the grid or `None` if it is a hole
+    indices: Vec<Option<usize>>,
+    cells: Vec<GridCell>,
+}
+
+impl BoundedGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::with_holes(width, height, std::iter::empty()).expect("there are no holes")
+    }
+
+    pub fn with_holes<I>(width: usize, height: usize, holes: I) -> Result<Self, HoleOutOfBounds>
+    where
+        I: IntoIterator<Item = GridCell>,
+    {
+        let mut is_hole = vec![false; width * height];
+        for (x, y) in holes {
+            if x >= width || y >= height {
+                return Err(HoleOutOfBounds {
+                    hole: (x, y),
+                    width,
+                    height,
+                });
+            }
+            is_hole[y * width + x] = true;
+        }
+
+        let mut indices = Vec::with_capacity(width * height);
+        let mut cells = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                if is_hole[y * width + x] {
+                    indices.push(None);
+                } else {
+                    indices.push(Some(cells.len()));
+                    cells.push((x, y));
+                }
+            }
+        }
+
+        Ok(BoundedGrid {
+            width,
+            height,
+            indices,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): GridCell) -> bool {
+        x < self.width && y < self.height && self.indices[y * self.width + x].is_some()
+    }
+}
+
+/// Iterator over the cells surrounding the origin in a `BoundedGrid`.
+pub struct GridNeighbours<'a> {
+    grid: &'a BoundedGrid,
+    origin: GridCell,
+    // position in the 3x3 square centered at the origin
+    offset: usize,
+}
+
+impl Iterator for GridNeighbours<'_> {
+    type Item = GridCell;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.offset < 9 {
+            let offset = self.offset;
+            self.offset += 1;
+            if offset == 4 {
+                // that's the origin itself
+                continue;
+            }
+
+            let x = (self.origin.0 + offset % 3).checked_sub(1);
+            let y = (self.origin.1 + offset / 3).checked_sub(1);
+            if let (Some(x), Some(y)) = (x, y) {
+                if self.grid.contains((x, y)) {
+                    return Some((x, y));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl Topology for BoundedGrid {
+    type Cell = GridCell;
+    type Neighbours<'a> = GridNeighbours<'a>;
+
+    fn neighbours<'a>(&'a self, cell: &GridCell) -> Self::Neighbours<'a> {
+        GridNeighbours {
+            grid: self,
+            origin: *cell,
+            offset: 0,
+        }
+    }
+}
+
+impl FiniteTopology for BoundedGrid {
+    fn num_cells(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn cell(&self, index: usize) -> GridCell {
+        self.cells[index]
+    }
+
+    fn index(&self, &(x, y): &GridCell) -> Option<usize> {
+        if x < self.width && y < self.height {
+            self.indices[y * self.width + x]
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn moore_neighbourhood_size() {
+        assert_eq!(8, Moore.neighbours(&Point::<2>::origin()).count());
+        assert_eq!(26, Moore.neighbours(&Point::<3>::origin()).count());
+        assert_eq!(80, Moore.neighbours(&Point::<4>::origin()).count());
+    }
+
+    #[test]
+    fn mirrored_neighbours_are_weighted_by_adjacent_mirror_images() {
+        let topology = MirroredMoore::<2>::new(1);
+
+        // (0, 1) and its mirror (0, -1) are both adjacent to (0, 0)
+        let neighbours: Vec<_> = topology.neighbours(&Point([0, 1])).collect();
+        assert_eq!(
+            2,
+            neighbours.iter().filter(|&&n| n == Point([0, 0])).count()
+        );
+        assert_eq!(
+            1,
+            neighbours.iter().filter(|&&n| n == Point([0, 2])).count()
+        );
+        assert!(neighbours.iter().all(|n| n.0[1] >= 0));
+
+        // while only the points themselves are adjacent to the mirror plane
+        let neighbours: Vec<_> = topology.neighbours(&Point([0, 0])).collect();
+        assert_eq!(5, neighbours.len());
+    }
+
+    #[test]
+    fn mirrored_multiplicity() {
+        let topology = MirroredMoore::<4>::new(2);
+
+        assert_eq!(1, topology.multiplicity(&Point([3, -1, 0, 0])));
+        assert_eq!(2, topology.multiplicity(&Point([3, -1, 0, 5])));
+        assert_eq!(4, topology.multiplicity(&Point([3, -1, 1, 5])));
+    }
+
+    #[test]
+    fn hex_neighbours_are_distinct_and_adjacent() {
+        let origin = Point([2, -1]);
+        let neighbours: HashSet<_> = HexAxial.neighbours(&origin).collect();
+
+        assert_eq!(6, neighbours.len());
+        for neighbour in neighbours {
+            // the adjacency is symmetric, so the origin neighbours each of its neighbours
+            assert!(HexAxial.neighbours(&neighbour).any(|n| n == origin));
+        }
+    }
+
+    #[test]
+    fn bounded_grid_neighbours_stay_within_bounds() {
+        let grid = BoundedGrid::new(3, 2);
+
+        let corner: HashSet<_> = grid.neighbours(&(0, 0)).collect();
+        let expected: HashSet<_> = vec![(1, 0), (0, 1), (1, 1)].into_iter().collect();
+        assert_eq!(expected, corner);
+
+        assert_eq!(5, grid.neighbours(&(1, 1)).count());
+    }
+
+    #[test]
+    fn bounded_grid_skips_holes() {
+        let grid = BoundedGrid::with_holes(3, 3, vec![(1, 1), (2, 2)]).unwrap();
+
+        assert_eq!(7, grid.num_cells());
+        assert_eq!(None, grid.index(&(1, 1)));
+        assert_eq!(None, grid.index(&(3, 0)));
+        assert_eq!(
+            Some((2, 1)),
+            grid.index(&(2, 1)).map(|index| grid.cell(index))
+        );
+        assert_eq!(3, grid.neighbours(&(2, 1)).count());
+    }
+
+    #[test]
+    fn holes_outside_the_grid_are_rejected() {
+        // past the end of a row, which would otherwise wrap onto the next one
+        assert_eq!(
+            HoleOutOfBounds {
+                hole: (3, 0),
+                width: 3,
+                height: 2
+            },
+            BoundedGrid::with_holes(3, 2, vec![(1, 1), (3, 0)]).unwrap_err()
+        );
+        // and below the last row
+        assert_eq!(
+            HoleOutOfBounds {
+                hole: (0, 2),
+                width: 3,
+                height: 2
+            },
+            BoundedGrid::with_holes(3, 2, vec![(0, 2)]).unwrap_err()
+        );
+    }
+}
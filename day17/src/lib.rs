@@ -12,62 +12,184 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::ops::{Add, AddAssign};
+use automaton::{LifeRule, MirroredMoore, Moore, SparseAutomaton, Topology};
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::path::Path;
+use utils::input_read::{self, InputError};
+use utils::solution::Solution;
 
-// Point contains list of values for each dimension
-#[derive(Debug, Eq, PartialEq, Clone, Hash)]
-pub struct Point(pub Vec<isize>);
+pub use point::{AdjacentPoints, DimensionMismatch, Point};
 
-impl Point {
-    pub fn base(dims: usize) -> Point {
-        Point(vec![0; dims])
+pub mod automaton;
+mod point;
+
+const ACTIVE_CUBE: char = '#';
+const NUM_CYCLES: usize = 6;
+const PART1_DIMENSIONS: usize = 3;
+const PART2_DIMENSIONS: usize = 4;
+
+fn parse_initial_data<const N: usize>(input: &[String]) -> HashSet<Point<N>> {
+    let mut active_cubes = HashSet::new();
+    for (y, raw_row) in input.iter().enumerate() {
+        raw_row.chars().enumerate().for_each(|(x, char)| {
+            if char == ACTIVE_CUBE {
+                let mut coords = [0; N];
+                coords[0] = x as isize;
+                coords[1] = y as isize;
+                active_cubes.insert(Point(coords));
+            }
+        })
     }
 
-    pub fn dimension_adjacent(&self, dim: usize) -> Vec<Point> {
-        let mut positively_adjacent = self.clone();
-        positively_adjacent.0[dim - 1] += 1;
+    active_cubes
+}
 
-        let mut negatively_adjacent = self.clone();
-        negatively_adjacent.0[dim - 1] -= 1;
+/// Determines how the cubes are simulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimulationMode {
+    /// Every active cube is tracked explicitly.
+    Full,
+    /// As the initial state is a 2D slice, the pocket dimension stays mirror-symmetric in every
+    /// extra dimension, so only cubes with non-negative extra coordinates have to be tracked.
+    Symmetric,
+}
 
-        vec![positively_adjacent, negatively_adjacent]
-    }
+fn run_cycles<T, const N: usize>(
+    topology: T,
+    input: &[String],
+    cycles: usize,
+    parallel: bool,
+) -> SparseAutomaton<T, LifeRule>
+where
+    T: Topology<Cell = Point<N>> + Sync,
+{
+    assert!(N >= 2, "the initial state is 2 dimensional");
 
-    pub fn adjacent_points(&self) -> Vec<Point> {
-        let mut adjacent = Vec::with_capacity((3usize.pow(self.0.len() as u32 - 1)) as usize);
+    let mut cubes = SparseAutomaton::new(
+        topology,
+        LifeRule::new(&[3], &[2, 3]),
+        parse_initial_data(input),
+    );
 
-        for dim in 1..=self.0.len() {
-            let mut dim_adjacent = Vec::new();
-            for adj in adjacent.iter().chain(std::iter::once(self)) {
-                dim_adjacent.append(&mut adj.dimension_adjacent(dim))
-            }
-            adjacent.extend(dim_adjacent.into_iter().filter(|p| p != self))
+    for _ in 0..cycles {
+        if parallel {
+            cubes.step_par();
+        } else {
+            cubes.step();
         }
+    }
+
+    cubes
+}
 
-        adjacent
+fn count_active<const N: usize>(
+    input: &[String],
+    cycles: usize,
+    mode: SimulationMode,
+    parallel: bool,
+) -> usize {
+    match mode {
+        SimulationMode::Full => run_cycles(Moore::<N>, input, cycles, parallel).alive_count(),
+        SimulationMode::Symmetric => {
+            let cubes = run_cycles(MirroredMoore::<N>::new(2), input, cycles, parallel);
+            cubes
+                .alive_cells()
+                .map(|cube| cubes.topology().multiplicity(cube))
+                .sum()
+        }
     }
 }
 
-impl Add<(isize, isize, isize)> for &Point {
-    type Output = Point;
+/// Runs the specified number of cycles in N dimensional space and returns the number of
+/// active cubes afterwards.
+pub fn simulate<const N: usize>(input: &[String], cycles: usize, mode: SimulationMode) -> usize {
+    count_active::<N>(input, cycles, mode, false)
+}
+
+/// Parallel equivalent of `simulate`.
+pub fn simulate_par<const N: usize>(
+    input: &[String],
+    cycles: usize,
+    mode: SimulationMode,
+) -> usize {
+    count_active::<N>(input, cycles, mode, true)
+}
+
+pub fn part1(input: &[String]) -> usize {
+    simulate::<PART1_DIMENSIONS>(input, NUM_CYCLES, SimulationMode::Symmetric)
+}
+
+pub fn part2(input: &[String]) -> usize {
+    simulate_par::<PART2_DIMENSIONS>(input, NUM_CYCLES, SimulationMode::Symmetric)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Vec<String>;
+    type ParseError = Infallible;
+    type Part1Output = usize;
+    type Part2Output = usize;
 
-    fn add(self, rhs: (isize, isize, isize)) -> Self::Output {
-        assert_eq!(3, self.0.len());
+    fn parse_input<P: AsRef<Path>>(path: P) -> Result<Self::Input, InputError<Self::ParseError>> {
+        input_read::read_line_input(path)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Part1Output> {
+        Some(part1(input))
+    }
 
-        Point(vec![
-            self.0[0] + rhs.0,
-            self.0[1] + rhs.1,
-            self.0[2] + rhs.2,
-        ])
+    fn part2(input: &Self::Input) -> Option<Self::Part2Output> {
+        Some(part2(input))
     }
 }
 
-impl AddAssign<(isize, isize, isize)> for Point {
-    fn add_assign(&mut self, rhs: (isize, isize, isize)) {
-        assert_eq!(3, self.0.len());
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_sample_input() {
+        let input = vec![".#.".to_string(), "..#".to_string(), "###".to_string()];
+
+        let expected = 112;
+
+        assert_eq!(expected, part1(&input))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = vec![".#.".to_string(), "..#".to_string(), "###".to_string()];
+
+        let expected = 848;
+
+        assert_eq!(expected, part2(&input))
+    }
+
+    #[test]
+    fn symmetric_simulation_matches_full_simulation() {
+        let input = vec![".#.".to_string(), "..#".to_string(), "###".to_string()];
+
+        for cycles in 0..=NUM_CYCLES {
+            assert_eq!(
+                simulate::<3>(&input, cycles, SimulationMode::Full),
+                simulate::<3>(&input, cycles, SimulationMode::Symmetric)
+            );
+            assert_eq!(
+                simulate_par::<4>(&input, cycles, SimulationMode::Full),
+                simulate_par::<4>(&input, cycles, SimulationMode::Symmetric)
+            );
+        }
+    }
+
+    #[test]
+    fn higher_dimensions() {
+        let input = vec![".#.".to_string(), "..#".to_string(), "###".to_string()];
 
-        self.0[0] += rhs.0;
-        self.0[1] += rhs.1;
-        self.0[2] += rhs.2;
+        assert_eq!(
+            5760,
+            simulate_par::<5>(&input, NUM_CYCLES, SimulationMode::Symmetric)
+        );
     }
 }
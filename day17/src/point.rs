@@ -0,0 +1,227 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+// Point contains value for each of its N dimensions
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub struct Point<const N: usize>(pub [isize; N]);
+
+/// Error returned when converting coordinates of wrong dimension into a `Point`.
+#[derive(Debug, Eq, PartialEq)]
+pub struct DimensionMismatch {
+    pub expected: usize,
+    pub actual: usize,
+}
+
+impl<const N: usize> Point<N> {
+    pub const fn new(coords: [isize; N]) -> Self {
+        Point(coords)
+    }
+
+    pub const fn origin() -> Self {
+        Point([0; N])
+    }
+
+    /// Returns iterator over all 3^N - 1 points whose coordinates differ by at most one
+    /// in every dimension.
+    pub fn adjacent_points(&self) -> AdjacentPoints<N> {
+        AdjacentPoints {
+            center: *self,
+            offset: 0,
+        }
+    }
+}
+
+/// Iterator over points adjacent to the center one.
+#[derive(Debug, Clone)]
+pub struct AdjacentPoints<const N: usize> {
+    center: Point<N>,
+    // digits of the offset in base 3 represent the translation in each dimension
+    offset: usize,
+}
+
+impl<const N: usize> AdjacentPoints<N> {
+    const NUM_OFFSETS: usize = 3usize.pow(N as u32);
+    // offset with all digits being 1, i.e. translation of 0 in every dimension
+    const CENTER_OFFSET: usize = (Self::NUM_OFFSETS - 1) / 2;
+}
+
+impl<const N: usize> Iterator for AdjacentPoints<N> {
+    type Item = Point<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset == Self::CENTER_OFFSET {
+            self.offset += 1;
+        }
+        if self.offset >= Self::NUM_OFFSETS {
+            return None;
+        }
+
+        let mut point = self.center;
+        let mut remaining = self.offset;
+        for coord in point.0.iter_mut() {
+            *coord += (remaining % 3) as isize - 1;
+            remaining /= 3;
+        }
+        self.offset += 1;
+
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let mut remaining = Self::NUM_OFFSETS.saturating_sub(self.offset);
+        if self.offset <= Self::CENTER_OFFSET {
+            remaining -= 1;
+        }
+        (remaining, Some(remaining))
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for AdjacentPoints<N> {}
+
+impl<const N: usize> From<[isize; N]> for Point<N> {
+    fn from(coords: [isize; N]) -> Self {
+        Point(coords)
+    }
+}
+
+impl<const N: usize> TryFrom<&[isize]> for Point<N> {
+    type Error = DimensionMismatch;
+
+    fn try_from(coords: &[isize]) -> Result<Self, Self::Error> {
+        <[isize; N]>::try_from(coords)
+            .map(Point)
+            .map_err(|_| DimensionMismatch {
+                expected: N,
+                actual: coords.len(),
+            })
+    }
+}
+
+impl<const N: usize> TryFrom<Vec<isize>> for Point<N> {
+    type Error = DimensionMismatch;
+
+    fn try_from(coords: Vec<isize>) -> Result<Self, Self::Error> {
+        Point::try_from(coords.as_slice())
+    }
+}
+
+impl<const N: usize> From<Point<N>> for Vec<isize> {
+    fn from(point: Point<N>) -> Self {
+        point.0.to_vec()
+    }
+}
+
+impl<const N: usize> Add for Point<N> {
+    type Output = Point<N>;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<const N: usize> AddAssign for Point<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (coord, rhs_coord) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *coord += rhs_coord;
+        }
+    }
+}
+
+impl<const N: usize> Sub for Point<N> {
+    type Output = Point<N>;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<const N: usize> SubAssign for Point<N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (coord, rhs_coord) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *coord -= rhs_coord;
+        }
+    }
+}
+
+impl<const N: usize> Neg for Point<N> {
+    type Output = Point<N>;
+
+    fn neg(mut self) -> Self::Output {
+        for coord in self.0.iter_mut() {
+            *coord = -*coord;
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn adjacent_points_are_distinct_and_exclude_center() {
+        let center = Point([1, -2, 3]);
+        let adjacent: HashSet<_> = center.adjacent_points().collect();
+
+        assert_eq!(26, adjacent.len());
+        assert!(!adjacent.contains(&center));
+        assert!(adjacent
+            .iter()
+            .all(|point| (*point - center).0.iter().all(|diff| diff.abs() <= 1)));
+    }
+
+    #[test]
+    fn adjacent_points_report_exact_size() {
+        let mut adjacent = Point::<4>::origin().adjacent_points();
+        assert_eq!(80, adjacent.len());
+
+        adjacent.nth(50);
+        assert_eq!(29, adjacent.len());
+        assert_eq!(29, adjacent.count());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Point([1, 2, 3]);
+        let b = Point([-4, 0, 2]);
+
+        assert_eq!(Point([-3, 2, 5]), a + b);
+        assert_eq!(Point([5, 2, 1]), a - b);
+        assert_eq!(Point([-1, -2, -3]), -a);
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(b, c);
+    }
+
+    #[test]
+    fn conversion_from_vec() {
+        assert_eq!(Ok(Point([1, 2, 3])), Point::try_from(vec![1, 2, 3]));
+        assert_eq!(
+            Err(DimensionMismatch {
+                expected: 4,
+                actual: 3
+            }),
+            Point::<4>::try_from(vec![1, 2, 3])
+        );
+        assert_eq!(vec![4, 5], Vec::from(Point([4, 5])));
+    }
+}
@@ -0,0 +1,468 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::{ExpressionError, ParseError};
+use crate::evaluator::{pop_operand, to_rpn, Environment, Function, RpnToken};
+use crate::number::{BigInt, Number};
+use crate::operator::{Associativity, Operator, OperatorTable};
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expression<N = i64> {
+    Number(N),
+    Variable(String),
+    Unary {
+        operator: Operator,
+        operand: Box<Expression<N>>,
+    },
+    Binary {
+        operator: Operator,
+        left: Box<Expression<N>>,
+        right: Box<Expression<N>>,
+    },
+    Call {
+        function: Function,
+        arguments: Vec<Expression<N>>,
+    },
+}
+
+// how tightly the expression is bound together when printed without parentheses
+enum Binding {
+    Atom,
+    Operator(Operator),
+}
+
+/// Parses the expression into a tree using provided operator rules.
+pub fn parse<N: Number>(
+    input: &str,
+    operators: &OperatorTable,
+) -> Result<Expression<N>, ParseError> {
+    let mut stack: Vec<Expression<N>> = Vec::new();
+
+    // the tree is built from the same representation the evaluator uses, so that both of them
+    // always agree on how the expression should be understood
+    for token in to_rpn(input, operators)? {
+        let expression = match token {
+            RpnToken::Number(number) => Expression::Number(number),
+            RpnToken::Variable(name) => Expression::Variable(name),
+            RpnToken::Operator(operator) if operator.is_unary() => {
+                let operand = pop_operand(&mut stack);
+                // negated literals are kept as negative numbers, which is also how constant
+                // folding produces them, so that printing them back yields the same tree
+                let negated = match (operator, &operand) {
+                    (Operator::Negation, Expression::Number(number)) => number.checked_neg(),
+                    _ => None,
+                };
+                match negated {
+                    Some(number) => Expression::Number(number),
+                    None => Expression::Unary {
+                        operator,
+                        operand: Box::new(operand),
+                    },
+                }
+            }
+            RpnToken::Operator(operator) => {
+                let right = pop_operand(&mut stack);
+                let left = pop_operand(&mut stack);
+                Expression::Binary {
+                    operator,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                }
+            }
+            RpnToken::Call {
+                function,
+                arguments,
+            } => Expression::Call {
+                function,
+                arguments: stack.split_off(stack.len() - arguments),
+            },
+        };
+        stack.push(expression)
+    }
+
+    Ok(pop_operand(&mut stack))
+}
+
+/// Shows how the expression gets parsed with the provided operator rules
+/// by explicitly parenthesizing every operation.
+pub fn explain(input: &str, operators: &OperatorTable) -> Result<String, ParseError> {
+    // the numbers are only printed back, so any literal is accepted
+    parse::<BigInt>(input, operators).map(|expression| expression.to_string())
+}
+
+impl<N: Number> Expression<N> {
+    pub fn evaluate(&self, environment: &Environment<N>) -> Result<N, ExpressionError> {
+        match self {
+            Expression::Number(number) => Ok(number.clone()),
+            Expression::Variable(name) => environment
+                .get(name)
+                .cloned()
+                .ok_or_else(|| ExpressionError::UnknownVariable(name.clone())),
+            Expression::Unary { operator, operand } => {
+                operator.apply_unary(operand.evaluate(environment)?)
+            }
+            Expression::Binary {
+                operator,
+                left,
+                right,
+            } => operator.apply(left.evaluate(environment)?, right.evaluate(environment)?),
+            Expression::Call {
+                function,
+                arguments,
+            } => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| argument.evaluate(environment))
+                    .collect::<Result<Vec<_>, _>>()?;
+                function.apply(&arguments)
+            }
+        }
+    }
+
+    /// Evaluates all subexpressions that do not depend on any variables. Operations that
+    /// would fail, such as division by zero, are left intact so that the error is reported
+    /// when the expression gets evaluated.
+    pub fn fold_constants(self) -> Expression<N> {
+        let folded = match self {
+            Expression::Unary { operator, operand } => Expression::Unary {
+                operator,
+                operand: Box::new(operand.fold_constants()),
+            },
+            Expression::Binary {
+                operator,
+                left,
+                right,
+            } => Expression::Binary {
+                operator,
+                left: Box::new(left.fold_constants()),
+                right: Box::new(right.fold_constants()),
+            },
+            Expression::Call {
+                function,
+                arguments,
+            } => Expression::Call {
+                function,
+                arguments: arguments.into_iter().map(Self::fold_constants).collect(),
+            },
+            leaf => return leaf,
+        };
+
+        let is_constant = match &folded {
+            Expression::Unary { operand, .. } => operand.is_number(),
+            Expression::Binary { left, right, .. } => left.is_number() && right.is_number(),
+            Expression::Call { arguments, .. } => arguments.iter().all(Self::is_number),
+            _ => false,
+        };
+
+        if is_constant {
+            if let Ok(value) = folded.evaluate(&Environment::new()) {
+                return Expression::Number(value);
+            }
+        }
+        folded
+    }
+
+    fn is_number(&self) -> bool {
+        matches!(self, Expression::Number(_))
+    }
+
+    fn binding(&self) -> Binding {
+        match self {
+            Expression::Number(number) if number.is_negative() => {
+                Binding::Operator(Operator::Negation)
+            }
+            Expression::Unary { operator, .. } | Expression::Binary { operator, .. } => {
+                Binding::Operator(*operator)
+            }
+            _ => Binding::Atom,
+        }
+    }
+
+    /// Formats the expression using as few parentheses as possible while still being parsed
+    /// back into the same tree with the provided operator rules.
+    pub fn pretty(&self, operators: &OperatorTable) -> String {
+        match self {
+            Expression::Number(number) => number.to_string(),
+            Expression::Variable(name) => name.clone(),
+            Expression::Unary { operator, operand } => {
+                let precedence = operators.precedence(*operator);
+                // operand is only bound to the operator if it binds tighter
+                let needs_parentheses = match operand.binding() {
+                    Binding::Atom => false,
+                    Binding::Operator(inner) if inner.is_unary() => false,
+                    Binding::Operator(inner) => {
+                        let inner_precedence = operators.precedence(inner);
+                        inner_precedence < precedence
+                            || (inner_precedence == precedence
+                                && operators.associativity(inner) == Associativity::Left)
+                    }
+                };
+                format!(
+                    "{}{}",
+                    operator,
+                    parenthesize(operand.pretty(operators), needs_parentheses)
+                )
+            }
+            Expression::Binary {
+                operator,
+                left,
+                right,
+            } => {
+                let precedence = operators.precedence(*operator);
+                let associativity = operators.associativity(*operator);
+
+                // left operand is complete once an operator that binds looser arrives
+                let left_parentheses = match left.binding() {
+                    Binding::Atom => false,
+                    Binding::Operator(inner) => {
+                        let inner_precedence = operators.precedence(inner);
+                        inner_precedence < precedence
+                            || (inner_precedence == precedence
+                                && associativity == Associativity::Right)
+                    }
+                };
+
+                // while the right one has to bind tighter not to be cut short by the operator.
+                // Prefix operators on the right are parenthesized whenever they bind looser,
+                // otherwise they could swallow whatever follows the entire expression
+                let right_parentheses = match right.binding() {
+                    Binding::Atom => false,
+                    Binding::Operator(inner) if inner.is_unary() => {
+                        operators.precedence(inner) < precedence
+                    }
+                    Binding::Operator(inner) => {
+                        let inner_precedence = operators.precedence(inner);
+                        inner_precedence < precedence
+                            || (inner_precedence == precedence
+                                && operators.associativity(inner) == Associativity::Left)
+                    }
+                };
+
+                format!(
+                    "{} {} {}",
+                    parenthesize(left.pretty(operators), left_parentheses),
+                    operator,
+                    parenthesize(right.pretty(operators), right_parentheses)
+                )
+            }
+            Expression::Call {
+                function,
+                arguments,
+            } => format!(
+                "{}({})",
+                function.name(),
+                arguments
+                    .iter()
+                    .map(|argument| argument.pretty(operators))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+fn parenthesize(formatted: String, needs_parentheses: bool) -> String {
+    if needs_parentheses {
+        format!("({})", formatted)
+    } else {
+        formatted
+    }
+}
+
+// fully parenthesized form that is unambiguous regardless of the operator rules
+impl<N: Number> Display for Expression<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::Number(number) if number.is_negative() => write!(f, "({})", number),
+            Expression::Number(number) => write!(f, "{}", number),
+            Expression::Variable(name) => write!(f, "{}", name),
+            Expression::Unary { operator, operand } => write!(f, "({}{})", operator, operand),
+            Expression::Binary {
+                operator,
+                left,
+                right,
+            } => write!(f, "({} {} {})", left, operator, right),
+            Expression::Call {
+                function,
+                arguments,
+            } => {
+                write!(f, "{}(", function.name())?;
+                for (i, argument) in arguments.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", argument)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluate;
+
+    fn homework_rules() -> OperatorTable {
+        OperatorTable::new()
+            .with_precedence(Operator::Multiplication, 1)
+            .with_precedence(Operator::Addition, 2)
+    }
+
+    #[test]
+    fn tree_matches_the_evaluator() {
+        let mut environment: Environment = Environment::new();
+        environment.insert("x".to_string(), 3);
+
+        for operators in &[OperatorTable::new(), homework_rules()] {
+            for input in &[
+                "2 * 3 + (4 * 5)",
+                "5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))",
+                "-x ^ 2 - max(x, 2 * -x) % 2",
+                "2 ^ 3 ^ 2 - 10 / 3 / 2",
+            ] {
+                assert_eq!(
+                    evaluate(input, operators, &environment),
+                    parse(input, operators).unwrap().evaluate(&environment)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn explaining_precedence() {
+        assert_eq!(
+            Ok("((2 * 3) + 4)".to_string()),
+            explain("2 * 3 + 4", &OperatorTable::new())
+        );
+        assert_eq!(
+            Ok("(2 * (3 + 4))".to_string()),
+            explain("2 * 3 + 4", &homework_rules())
+        );
+        assert_eq!(
+            Ok("((-2) ^ (-x))".to_string()),
+            explain("(-2) ^ -x", &OperatorTable::new())
+        );
+    }
+
+    #[test]
+    fn pretty_printing_uses_minimal_parentheses() {
+        let operators = OperatorTable::new();
+        for (input, expected) in &[
+            ("((1 + 2)) + (3 * 4)", "1 + 2 + 3 * 4"),
+            ("1 - (2 - 3)", "1 - (2 - 3)"),
+            ("(2 ^ 3) ^ 2", "(2 ^ 3) ^ 2"),
+            ("2 ^ (3 ^ 2)", "2 ^ 3 ^ 2"),
+            ("-(x ^ 2)", "-x ^ 2"),
+            ("(-x) ^ 2", "(-x) ^ 2"),
+            ("-(x * 2)", "-(x * 2)"),
+            ("max((1 + 2), (x))", "max(1 + 2, x)"),
+        ] {
+            assert_eq!(
+                *expected,
+                parse::<i64>(input, &operators).unwrap().pretty(&operators)
+            );
+        }
+
+        // the same tree needs different parentheses under different rules
+        let tree = parse::<i64>("1 + 2 * 3", &OperatorTable::new()).unwrap();
+        assert_eq!("1 + (2 * 3)", tree.pretty(&homework_rules()));
+    }
+
+    #[test]
+    fn pretty_printing_round_trips() {
+        for operators in &[OperatorTable::new(), homework_rules()] {
+            for input in &[
+                "(2 * 3 + 4) * (5 + -x)",
+                "-(2 + 3) * -(-4 ^ 2) - (1 - 2 - 3)",
+                "2 ^ (-3 % 2) ^ min(1, 2 - (3 - 4))",
+            ] {
+                let expression = parse::<i64>(input, operators).unwrap();
+                let printed = expression.pretty(operators);
+                assert_eq!(
+                    expression,
+                    parse(&printed, operators).unwrap(),
+                    "{}",
+                    printed
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn folded_negative_literals_round_trip() {
+        let operators = OperatorTable::new();
+        for input in &[
+            "x - (1 + 3) * 2",
+            "(2 - 4) ^ 2",
+            "x ^ (1 - 2)",
+            "-(3 - 1) ^ x",
+        ] {
+            let folded = parse::<i64>(input, &operators).unwrap().fold_constants();
+            let printed = folded.pretty(&operators);
+            assert_eq!(folded, parse(&printed, &operators).unwrap(), "{}", printed);
+        }
+
+        assert_eq!(
+            Expression::Number(-4),
+            parse::<i64>("-4", &operators).unwrap()
+        );
+        // negation applies to the entire power rather than just the literal
+        assert_eq!(
+            "-4 ^ 2",
+            parse::<i64>("-4 ^ 2", &operators)
+                .unwrap()
+                .pretty(&operators)
+        );
+        assert_eq!(
+            Ok(-16),
+            parse::<i64>("-4 ^ 2", &operators)
+                .unwrap()
+                .evaluate(&Environment::new())
+        );
+        // literals that can't be negated are left for the evaluation to report
+        assert_eq!(
+            Err(ExpressionError::Overflow),
+            parse::<u64>("-4", &operators)
+                .unwrap()
+                .evaluate(&Environment::new())
+        );
+    }
+
+    #[test]
+    fn constant_folding() {
+        let operators = OperatorTable::new();
+        let folded = parse::<i64>("x * (2 + 3) - max(1, 4) / (1 - 1)", &operators)
+            .unwrap()
+            .fold_constants();
+
+        assert_eq!("x * 5 - 4 / 0", folded.pretty(&operators));
+        assert_eq!(
+            Ok(-3),
+            parse::<i64>("-(1 + 2)", &operators)
+                .unwrap()
+                .fold_constants()
+                .evaluate(&Environment::new())
+        );
+        assert_eq!(
+            "2 ^ (-3)",
+            parse::<i64>("2 ^ -3", &operators)
+                .unwrap()
+                .fold_constants()
+                .pretty(&operators)
+        );
+    }
+}
@@ -0,0 +1,151 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{self, Display, Formatter};
+
+/// Location of a token within the original expression, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub offset: usize,
+    pub length: usize,
+}
+
+impl Span {
+    pub fn new(offset: usize, length: usize) -> Self {
+        Span { offset, length }
+    }
+
+    pub fn end(&self) -> usize {
+        self.offset + self.length
+    }
+
+    // smallest span covering both of the spans
+    pub(crate) fn to(self, other: Span) -> Span {
+        Span::new(self.offset, other.end() - self.offset)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidCharacter(char),
+    NumberTooLarge,
+    UnknownFunction(String),
+    UnclosedParenthesis,
+    UnexpectedClosingParenthesis,
+    /// An argument separator was found outside of any function call.
+    MisplacedComma,
+    /// The operator (or separator) is missing an operand on its side.
+    MissingOperand,
+    /// Two operands follow each other without an operator in between.
+    MissingOperator,
+    EmptyExpression,
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::InvalidCharacter(c) => write!(f, "invalid character '{}'", c),
+            ParseErrorKind::NumberTooLarge => write!(f, "number is too large"),
+            ParseErrorKind::UnknownFunction(name) => write!(f, "unknown function '{}'", name),
+            ParseErrorKind::UnclosedParenthesis => write!(f, "unclosed parenthesis"),
+            ParseErrorKind::UnexpectedClosingParenthesis => {
+                write!(f, "unexpected closing parenthesis")
+            }
+            ParseErrorKind::MisplacedComma => write!(f, "comma outside of a function call"),
+            ParseErrorKind::MissingOperand => write!(f, "missing operand"),
+            ParseErrorKind::MissingOperator => write!(f, "expected an operator"),
+            ParseErrorKind::EmptyExpression => write!(f, "empty expression"),
+        }
+    }
+}
+
+/// Syntax error pointing at the offending part of the expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Span,
+}
+
+impl ParseError {
+    pub(crate) fn new(kind: ParseErrorKind, span: Span) -> Self {
+        ParseError { kind, span }
+    }
+
+    /// Renders the error below the expression it came from with the offending token underlined.
+    pub fn render(&self, input: &str) -> String {
+        // the span is in bytes, but the terminal columns are in characters
+        let start = self.span.offset.min(input.len());
+        let end = self.span.end().min(input.len());
+        let padding = input[..start].chars().count();
+        let width = input[start..end].chars().count().max(1);
+
+        format!(
+            "{}\n{}{} {}",
+            input,
+            " ".repeat(padding),
+            "^".repeat(width),
+            self.kind
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.kind, self.span.offset)
+    }
+}
+
+/// Reasons for which an expression could not be evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpressionError {
+    Parse(ParseError),
+    UnknownVariable(String),
+    InvalidArgumentCount { function: String, count: usize },
+    DivisionByZero,
+    NegativeExponent,
+    Overflow,
+}
+
+impl From<ParseError> for ExpressionError {
+    fn from(err: ParseError) -> Self {
+        ExpressionError::Parse(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rendering_underlines_the_token() {
+        let error = ParseError::new(
+            ParseErrorKind::UnknownFunction("foo".into()),
+            Span::new(4, 3),
+        );
+        assert_eq!(
+            "1 + foo(2)\n    ^^^ unknown function 'foo'",
+            error.render("1 + foo(2)")
+        );
+
+        // columns are counted in characters rather than bytes
+        let error = ParseError::new(ParseErrorKind::InvalidCharacter('€'), Span::new(4, 3));
+        assert_eq!(
+            "1 + € 2\n    ^ invalid character '€'",
+            error.render("1 + € 2")
+        );
+
+        let error = ParseError::new(ParseErrorKind::EmptyExpression, Span::new(0, 0));
+        assert_eq!("\n^ empty expression", error.render(""));
+    }
+}
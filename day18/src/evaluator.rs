@@ -0,0 +1,498 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::{ExpressionError, ParseError, ParseErrorKind, Span};
+use crate::number::Number;
+use crate::operator::{Associativity, Operator, OperatorTable};
+use crate::tokenizer::{tokenize, Token};
+use std::collections::{HashMap, VecDeque};
+use std::convert::TryFrom;
+
+/// Values of the variables used in the expressions.
+pub type Environment<N = i64> = HashMap<String, N>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    Min,
+    Max,
+    Abs,
+}
+
+impl<'a> TryFrom<&'a str> for Function {
+    type Error = ParseErrorKind;
+
+    fn try_from(name: &'a str) -> Result<Self, Self::Error> {
+        match name {
+            "min" => Ok(Function::Min),
+            "max" => Ok(Function::Max),
+            "abs" => Ok(Function::Abs),
+            _ => Err(ParseErrorKind::UnknownFunction(name.to_owned())),
+        }
+    }
+}
+
+impl Function {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Function::Min => "min",
+            Function::Max => "max",
+            Function::Abs => "abs",
+        }
+    }
+
+    pub(crate) fn apply<N: Number>(&self, arguments: &[N]) -> Result<N, ExpressionError> {
+        let result = match (self, arguments) {
+            (Function::Abs, [x]) => x.checked_abs().ok_or(ExpressionError::Overflow)?,
+            (Function::Min, [first, rest @ ..]) => rest.iter().fold(first, |a, b| a.min(b)).clone(),
+            (Function::Max, [first, rest @ ..]) => rest.iter().fold(first, |a, b| a.max(b)).clone(),
+            _ => {
+                return Err(ExpressionError::InvalidArgumentCount {
+                    function: self.name().to_owned(),
+                    count: arguments.len(),
+                })
+            }
+        };
+
+        Ok(result)
+    }
+}
+
+struct Stack<T> {
+    inner: Vec<T>,
+}
+
+impl<T> Stack<T> {
+    fn new() -> Self {
+        Stack { inner: Vec::new() }
+    }
+
+    fn push(&mut self, val: T) {
+        self.inner.push(val)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.inner.pop()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.inner.last()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum RpnToken<N> {
+    Number(N),
+    Variable(String),
+    Operator(Operator),
+    Call {
+        function: Function,
+        arguments: usize,
+    },
+}
+
+enum StackItem {
+    Operator(Operator),
+    // opening parenthesis of either a group or a function call
+    Group(Span),
+    Call {
+        function: Function,
+        arguments: usize,
+        span: Span,
+    },
+}
+
+// moves operators to the queue until the innermost group or call is reached
+fn pop_until_group<N>(
+    stack: &mut Stack<StackItem>,
+    queue: &mut VecDeque<RpnToken<N>>,
+) -> Option<StackItem> {
+    while let Some(top) = stack.pop() {
+        match top {
+            StackItem::Operator(operator) => queue.push_back(RpnToken::Operator(operator)),
+            group => return Some(group),
+        }
+    }
+    None
+}
+
+// Besides reordering the tokens, this also validates the syntax of the expression,
+// so that the resulting queue is always well-formed
+fn shunting_yard<N: Number>(
+    input: &str,
+    tokens: Vec<(Token<N>, Span)>,
+    operators: &OperatorTable,
+) -> Result<VecDeque<RpnToken<N>>, ParseError> {
+    let mut queue = VecDeque::new();
+    let mut stack: Stack<StackItem> = Stack::new();
+
+    // whether an operand (rather than an operator) has to come next
+    let mut expects_operand = true;
+    let mut previous: Option<(Token<N>, Span)> = None;
+
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some((token, span)) = tokens.next() {
+        let missing_operand = |previous: &Option<(Token<N>, Span)>| {
+            // blame the token that was left without its operand
+            let span = previous.as_ref().map(|(_, span)| *span).unwrap_or(span);
+            ParseError::new(ParseErrorKind::MissingOperand, span)
+        };
+
+        let starts_operand = matches!(
+            token,
+            Token::Number(_) | Token::Identifier(_) | Token::LeftParen
+        ) || matches!(token, Token::Operator(operator) if operator.is_unary());
+        if starts_operand && !expects_operand {
+            return Err(ParseError::new(ParseErrorKind::MissingOperator, span));
+        }
+
+        match &token {
+            Token::Number(number) => {
+                queue.push_back(RpnToken::Number(number.clone()));
+                expects_operand = false;
+            }
+            Token::Identifier(name) if matches!(tokens.peek(), Some((Token::LeftParen, _))) => {
+                let function = Function::try_from(name.as_str())
+                    .map_err(|kind| ParseError::new(kind, span))?;
+                let (_, paren_span) = tokens.next().unwrap();
+                let arguments = if matches!(tokens.peek(), Some((Token::RightParen, _))) {
+                    0
+                } else {
+                    1
+                };
+                stack.push(StackItem::Call {
+                    function,
+                    arguments,
+                    span: span.to(paren_span),
+                });
+                previous = Some((Token::LeftParen, paren_span));
+                continue;
+            }
+            Token::Identifier(name) => {
+                queue.push_back(RpnToken::Variable(name.clone()));
+                expects_operand = false;
+            }
+            Token::LeftParen => stack.push(StackItem::Group(span)),
+            Token::RightParen => {
+                let group = pop_until_group(&mut stack, &mut queue).ok_or_else(|| {
+                    ParseError::new(ParseErrorKind::UnexpectedClosingParenthesis, span)
+                })?;
+                match group {
+                    StackItem::Call {
+                        function,
+                        arguments,
+                        ..
+                    } => {
+                        if expects_operand && arguments > 0 {
+                            return Err(missing_operand(&previous));
+                        }
+                        queue.push_back(RpnToken::Call {
+                            function,
+                            arguments,
+                        })
+                    }
+                    StackItem::Group(open) if expects_operand => {
+                        return Err(match previous {
+                            Some((Token::LeftParen, _)) => {
+                                ParseError::new(ParseErrorKind::EmptyExpression, open.to(span))
+                            }
+                            _ => missing_operand(&previous),
+                        })
+                    }
+                    _ => (),
+                }
+                expects_operand = false;
+            }
+            Token::Comma => {
+                if expects_operand {
+                    return Err(missing_operand(&previous));
+                }
+                match pop_until_group(&mut stack, &mut queue) {
+                    Some(StackItem::Call {
+                        function,
+                        arguments,
+                        span: call_span,
+                    }) => stack.push(StackItem::Call {
+                        function,
+                        arguments: arguments + 1,
+                        span: call_span,
+                    }),
+                    _ => return Err(ParseError::new(ParseErrorKind::MisplacedComma, span)),
+                }
+                expects_operand = true;
+            }
+            // prefix operators have nothing on their left to be bound to
+            Token::Operator(operator) if operator.is_unary() => {
+                stack.push(StackItem::Operator(*operator))
+            }
+            Token::Operator(operator) => {
+                if expects_operand {
+                    // nothing on the left of the operator
+                    return Err(ParseError::new(ParseErrorKind::MissingOperand, span));
+                }
+                let precedence = operators.precedence(*operator);
+                let associativity = operators.associativity(*operator);
+                while let Some(StackItem::Operator(top)) = stack.peek() {
+                    let top_precedence = operators.precedence(*top);
+                    if top_precedence > precedence
+                        || (top_precedence == precedence && associativity == Associativity::Left)
+                    {
+                        queue.push_back(RpnToken::Operator(*top));
+                        stack.pop();
+                    } else {
+                        break;
+                    }
+                }
+                stack.push(StackItem::Operator(*operator));
+                expects_operand = true;
+            }
+        }
+        previous = Some((token, span));
+    }
+
+    match previous {
+        // an opening parenthesis at the very end is reported as unclosed below
+        Some((Token::LeftParen, _)) => (),
+        Some((_, span)) if expects_operand => {
+            return Err(ParseError::new(ParseErrorKind::MissingOperand, span))
+        }
+        None => {
+            return Err(ParseError::new(
+                ParseErrorKind::EmptyExpression,
+                Span::new(0, input.len()),
+            ))
+        }
+        _ => (),
+    }
+
+    while let Some(item) = stack.pop() {
+        match item {
+            StackItem::Operator(operator) => queue.push_back(RpnToken::Operator(operator)),
+            StackItem::Group(span) | StackItem::Call { span, .. } => {
+                return Err(ParseError::new(ParseErrorKind::UnclosedParenthesis, span))
+            }
+        }
+    }
+
+    Ok(queue)
+}
+
+// the queue produced by the shunting yard is well-formed, so the operands are always there
+pub(crate) fn pop_operand<T>(stack: &mut Vec<T>) -> T {
+    stack
+        .pop()
+        .expect("the expression has been validated by the parser")
+}
+
+fn calculate<N: Number>(
+    mut rpn_queue: VecDeque<RpnToken<N>>,
+    environment: &Environment<N>,
+) -> Result<N, ExpressionError> {
+    let mut stack = Vec::new();
+
+    while let Some(token) = rpn_queue.pop_front() {
+        let value = match token {
+            RpnToken::Number(number) => number,
+            RpnToken::Variable(name) => environment
+                .get(&name)
+                .cloned()
+                .ok_or(ExpressionError::UnknownVariable(name))?,
+            RpnToken::Operator(operator) if operator.is_unary() => {
+                let x = pop_operand(&mut stack);
+                operator.apply_unary(x)?
+            }
+            RpnToken::Operator(operator) => {
+                let y = pop_operand(&mut stack);
+                let x = pop_operand(&mut stack);
+                operator.apply(x, y)?
+            }
+            RpnToken::Call {
+                function,
+                arguments,
+            } => {
+                let arguments = stack.split_off(stack.len() - arguments);
+                function.apply(&arguments)?
+            }
+        };
+        stack.push(value)
+    }
+
+    Ok(pop_operand(&mut stack))
+}
+
+// converts the expression into reverse polish notation
+pub(crate) fn to_rpn<N: Number>(
+    input: &str,
+    operators: &OperatorTable,
+) -> Result<VecDeque<RpnToken<N>>, ParseError> {
+    shunting_yard(input, tokenize(input)?, operators)
+}
+
+/// Evaluates the expression using provided operator rules and values of the variables.
+/// All the arithmetic is performed on the numeric type of the environment.
+pub fn evaluate<N: Number>(
+    input: &str,
+    operators: &OperatorTable,
+    environment: &Environment<N>,
+) -> Result<N, ExpressionError> {
+    calculate(to_rpn(input, operators)?, environment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::number::BigInt;
+
+    fn eval(input: &str) -> Result<i64, ExpressionError> {
+        evaluate(input, &OperatorTable::new(), &Environment::new())
+    }
+
+    #[test]
+    fn conventional_precedence_and_associativity() {
+        assert_eq!(Ok(7), eval("1 + 2 * 3"));
+        assert_eq!(Ok(-4), eval("1 - 2 - 3"));
+        assert_eq!(Ok(2), eval("20 / 5 / 2"));
+        assert_eq!(Ok(512), eval("2 ^ 3 ^ 2"));
+        assert_eq!(Ok(-4), eval("-2 ^ 2"));
+        assert_eq!(Ok(4), eval("(-2) ^ 2"));
+        assert_eq!(Ok(5), eval("3 - -2"));
+        assert_eq!(Ok(1), eval("10 % 3"));
+    }
+
+    #[test]
+    fn variables_and_functions() {
+        let mut environment: Environment = Environment::new();
+        environment.insert("x".to_string(), 4);
+        environment.insert("y".to_string(), -2);
+
+        let operators = OperatorTable::new();
+        assert_eq!(
+            Ok(6),
+            evaluate("max(x, y * 3, 1) + abs(y)", &operators, &environment)
+        );
+        assert_eq!(
+            Ok(-8),
+            evaluate("min(x * y, max(x), 0)", &operators, &environment)
+        );
+        assert_eq!(
+            Err(ExpressionError::UnknownVariable("z".to_string())),
+            evaluate("x + z", &operators, &environment)
+        );
+    }
+
+    #[test]
+    fn configurable_associativity() {
+        let operators = OperatorTable::new()
+            .with_associativity(Operator::Subtraction, Associativity::Right)
+            .with_associativity(Operator::Power, Associativity::Left);
+        let environment: Environment = Environment::new();
+
+        assert_eq!(Ok(2), evaluate("1 - 2 - 3", &operators, &environment));
+        assert_eq!(Ok(64), evaluate("2 ^ 3 ^ 2", &operators, &environment));
+    }
+
+    fn syntax_error(
+        kind: ParseErrorKind,
+        offset: usize,
+        length: usize,
+    ) -> Result<i64, ExpressionError> {
+        Err(ExpressionError::Parse(ParseError::new(
+            kind,
+            Span::new(offset, length),
+        )))
+    }
+
+    #[test]
+    fn syntax_errors() {
+        use ParseErrorKind::*;
+
+        assert_eq!(syntax_error(MissingOperand, 2, 1), eval("1 +"));
+        assert_eq!(syntax_error(MissingOperand, 0, 1), eval("* 2"));
+        assert_eq!(syntax_error(MissingOperand, 3, 1), eval("(1 *) + 2"));
+        assert_eq!(syntax_error(MissingOperand, 0, 1), eval("-"));
+        assert_eq!(syntax_error(MissingOperator, 2, 1), eval("1 2"));
+        assert_eq!(syntax_error(MissingOperator, 4, 1), eval("(1) (2)"));
+        assert_eq!(syntax_error(EmptyExpression, 0, 0), eval(""));
+        assert_eq!(syntax_error(EmptyExpression, 0, 3), eval("   "));
+        assert_eq!(syntax_error(EmptyExpression, 4, 3), eval("1 + ( )"));
+        assert_eq!(syntax_error(UnclosedParenthesis, 4, 1), eval("1 + ("));
+        assert_eq!(syntax_error(UnclosedParenthesis, 0, 1), eval("(1 + 2"));
+        assert_eq!(syntax_error(UnclosedParenthesis, 0, 4), eval("max(1, 2"));
+        assert_eq!(
+            syntax_error(UnexpectedClosingParenthesis, 5, 1),
+            eval("1 + 2)")
+        );
+        assert_eq!(syntax_error(MisplacedComma, 2, 1), eval("(1, 2)"));
+        assert_eq!(syntax_error(MissingOperand, 5, 1), eval("max(1,)"));
+        assert_eq!(
+            syntax_error(UnknownFunction("foo".to_string()), 0, 3),
+            eval("foo(1)")
+        );
+    }
+
+    #[test]
+    fn malformed_input_never_panics() {
+        let fragments = ["1", "x", "-", "+", "*", "(", ")", ",", "max("];
+        for a in &fragments {
+            for b in &fragments {
+                for c in &fragments {
+                    let input = format!("{}{}{}", a, b, c);
+                    let _ = evaluate(&input, &OperatorTable::new(), &Environment::<i64>::new());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn numeric_types() {
+        let operators = OperatorTable::new();
+
+        assert_eq!(
+            Err(ExpressionError::Overflow),
+            evaluate("1 - 2", &operators, &Environment::<u64>::new())
+        );
+        assert_eq!(
+            Ok(u64::MAX),
+            evaluate("18446744073709551615", &operators, &Environment::new())
+        );
+        assert_eq!(
+            Ok(i64::MAX as i128 + 1),
+            evaluate("9223372036854775807 + 1", &operators, &Environment::new())
+        );
+
+        let mut environment = Environment::new();
+        environment.insert("x".to_string(), BigInt::from(-3i64));
+        assert_eq!(
+            "-1546132562196033993109383389296863818106322566003",
+            evaluate::<BigInt>("x ^ 101 % 10 ^ 50", &operators, &environment)
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn evaluation_errors() {
+        assert_eq!(Err(ExpressionError::DivisionByZero), eval("1 / (2 - 2)"));
+        assert_eq!(
+            Err(ExpressionError::InvalidArgumentCount {
+                function: "max".to_string(),
+                count: 0
+            }),
+            eval("max()")
+        );
+        assert_eq!(
+            Err(ExpressionError::Overflow),
+            eval("9223372036854775807 + 1")
+        );
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::Infallible;
+use std::path::Path;
+use utils::input_read::{self, InputError};
+use utils::solution::Solution;
+
+pub use ast::{explain, parse, Expression};
+pub use error::{ExpressionError, ParseError, ParseErrorKind, Span};
+pub use evaluator::{evaluate, Environment, Function};
+pub use number::{BigInt, Number};
+pub use operator::{Associativity, Operator, OperatorTable};
+
+mod ast;
+mod error;
+mod evaluator;
+mod number;
+mod operator;
+mod tokenizer;
+
+/// Sums results of all the expressions, performing the arithmetic on the given numeric type.
+pub fn sum_homework<N: Number>(
+    input: &[String],
+    operators: &OperatorTable,
+) -> Result<N, ExpressionError> {
+    let environment = Environment::new();
+    input.iter().try_fold(N::zero(), |sum, raw| {
+        let result = evaluate(raw, operators, &environment)?;
+        sum.checked_add(&result).ok_or(ExpressionError::Overflow)
+    })
+}
+
+fn part1_operators() -> OperatorTable {
+    OperatorTable::new().with_precedence(Operator::Multiplication, 1)
+}
+
+fn part2_operators() -> OperatorTable {
+    OperatorTable::new()
+        .with_precedence(Operator::Multiplication, 1)
+        .with_precedence(Operator::Addition, 2)
+}
+
+pub fn part1(input: &[String]) -> i64 {
+    sum_homework(input, &part1_operators()).expect("failed to evaluate the homework")
+}
+
+pub fn part2(input: &[String]) -> i64 {
+    sum_homework(input, &part2_operators()).expect("failed to evaluate the homework")
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<String>;
+    type ParseError = Infallible;
+    type Part1Output = i64;
+    type Part2Output = i64;
+
+    fn parse_input<P: AsRef<Path>>(path: P) -> Result<Self::Input, InputError<Self::ParseError>> {
+        input_read::read_line_input(path)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Part1Output> {
+        sum_homework(input, &part1_operators()).ok()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2Output> {
+        sum_homework(input, &part2_operators()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_sample_input() {
+        let input = vec![
+            "2 * 3 + (4 * 5)".to_string(),
+            "5 + (8 * 3 + 9 + 3 * 4 * 3)".to_string(),
+            "5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))".to_string(),
+            "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2".to_string(),
+        ];
+
+        let expected = 26335;
+
+        assert_eq!(expected, part1(&input));
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = vec![
+            "2 * 3 + (4 * 5)".to_string(),
+            "5 + (8 * 3 + 9 + 3 * 4 * 3)".to_string(),
+            "5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))".to_string(),
+            "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2".to_string(),
+        ];
+
+        let expected = 693891;
+
+        assert_eq!(expected, part2(&input));
+    }
+
+    #[test]
+    fn homework_beyond_i64() {
+        let input = vec!["999999999999 * 999999999999 * 9 + 1".to_string(); 2];
+
+        assert_eq!(
+            Err(ExpressionError::Overflow),
+            sum_homework::<i64>(&input, &part1_operators())
+        );
+        assert_eq!(
+            Ok(BigInt::from(17999999999964000000000020u128)),
+            sum_homework(&input, &part1_operators())
+        );
+    }
+}
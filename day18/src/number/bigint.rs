@@ -0,0 +1,437 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Number;
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+
+// largest power of 10 that fits in a single digit
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+const DECIMAL_CHUNK_WIDTH: usize = 9;
+
+// results needing more digits than that (roughly ten thousand decimal digits) are treated
+// as overflowing, as the multiplication and division are quadratic and would otherwise
+// take hours on long inputs
+const MAX_DIGITS: usize = 1 << 10;
+
+/// Arbitrary-precision signed integer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    // little-endian digits in base 2^32 without leading zeros, so zero has no digits at all
+    magnitude: Vec<u32>,
+}
+
+impl BigInt {
+    fn new(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        trim(&mut magnitude);
+        BigInt {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+
+    fn negated(&self) -> Self {
+        BigInt::new(!self.negative, self.magnitude.clone())
+    }
+
+    fn bit_length(&self) -> u64 {
+        match self.magnitude.last() {
+            Some(highest) => self.magnitude.len() as u64 * 32 - u64::from(highest.leading_zeros()),
+            None => 0,
+        }
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        match (self.negative, self.magnitude.as_slice()) {
+            (false, []) => Some(0),
+            (false, [low]) => Some(*low as u64),
+            (false, [low, high]) => Some((*high as u64) << 32 | *low as u64),
+            _ => None,
+        }
+    }
+}
+
+impl From<u128> for BigInt {
+    fn from(value: u128) -> Self {
+        let magnitude = (0..4).map(|i| (value >> (32 * i)) as u32).collect();
+        BigInt::new(false, magnitude)
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> Self {
+        BigInt::new(value < 0, BigInt::from(value.unsigned_abs()).magnitude)
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(value: u64) -> Self {
+        BigInt::from(value as u128)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        BigInt::from(value as i128)
+    }
+}
+
+fn trim(magnitude: &mut Vec<u32>) {
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+}
+
+fn compare_magnitudes(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, &digit) in long.iter().enumerate() {
+        let sum = digit as u64 + short.get(i).copied().unwrap_or(0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> 32;
+    }
+    if carry > 0 {
+        result.push(carry as u32)
+    }
+    result
+}
+
+// `a` must not be smaller than `b`
+fn sub_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &digit) in a.iter().enumerate() {
+        let mut difference = digit as i64 - b.get(i).copied().unwrap_or(0) as i64 - borrow;
+        borrow = if difference < 0 {
+            difference += 1 << 32;
+            1
+        } else {
+            0
+        };
+        result.push(difference as u32);
+    }
+    trim(&mut result);
+    result
+}
+
+fn mul_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let current = result[i + j] as u64 + x as u64 * y as u64 + carry;
+            result[i + j] = current as u32;
+            carry = current >> 32;
+        }
+        result[i + b.len()] = carry as u32;
+    }
+    trim(&mut result);
+    result
+}
+
+fn shift_left_by_one(magnitude: &mut Vec<u32>) {
+    let mut carry = 0;
+    for digit in magnitude.iter_mut() {
+        let next_carry = *digit >> 31;
+        *digit = *digit << 1 | carry;
+        carry = next_carry;
+    }
+    if carry > 0 {
+        magnitude.push(carry)
+    }
+}
+
+// plain binary long division, `b` must not be zero
+fn divide_magnitudes(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder = Vec::new();
+    for bit in (0..a.len() * 32).rev() {
+        shift_left_by_one(&mut remainder);
+        if a[bit / 32] >> (bit % 32) & 1 == 1 {
+            match remainder.first_mut() {
+                Some(lowest) => *lowest |= 1,
+                None => remainder.push(1),
+            }
+        }
+        if compare_magnitudes(&remainder, b) != Ordering::Less {
+            remainder = sub_magnitudes(&remainder, b);
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    trim(&mut quotient);
+    (quotient, remainder)
+}
+
+// divides in place, returning the remainder
+fn divide_by_small(magnitude: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut remainder = 0u64;
+    for digit in magnitude.iter_mut().rev() {
+        let current = remainder << 32 | *digit as u64;
+        *digit = (current / divisor as u64) as u32;
+        remainder = current % divisor as u64;
+    }
+    trim(magnitude);
+    remainder as u32
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitudes(&self.magnitude, &other.magnitude),
+            (true, true) => compare_magnitudes(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.magnitude.is_empty() {
+            return write!(f, "0");
+        }
+
+        let mut magnitude = self.magnitude.clone();
+        let mut chunks = Vec::new();
+        while !magnitude.is_empty() {
+            chunks.push(divide_by_small(&mut magnitude, DECIMAL_CHUNK));
+        }
+
+        if self.negative {
+            write!(f, "-")?;
+        }
+        let mut chunks = chunks.iter().rev();
+        write!(f, "{}", chunks.next().unwrap())?;
+        for chunk in chunks {
+            write!(f, "{:0width$}", chunk, width = DECIMAL_CHUNK_WIDTH)?;
+        }
+        Ok(())
+    }
+}
+
+impl Number for BigInt {
+    fn from_u8(value: u8) -> Self {
+        BigInt::from(value as u64)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        if self.negative == other.negative {
+            return Some(BigInt::new(
+                self.negative,
+                add_magnitudes(&self.magnitude, &other.magnitude),
+            ));
+        }
+
+        // the sign of the result is the one of the operand with the greater magnitude
+        Some(
+            match compare_magnitudes(&self.magnitude, &other.magnitude) {
+                Ordering::Less => BigInt::new(
+                    other.negative,
+                    sub_magnitudes(&other.magnitude, &self.magnitude),
+                ),
+                _ => BigInt::new(
+                    self.negative,
+                    sub_magnitudes(&self.magnitude, &other.magnitude),
+                ),
+            },
+        )
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.checked_add(&other.negated())
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        if self.bit_length() + other.bit_length() > MAX_DIGITS as u64 * 32 {
+            return None;
+        }
+        Some(BigInt::new(
+            self.negative != other.negative,
+            mul_magnitudes(&self.magnitude, &other.magnitude),
+        ))
+    }
+
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+        let (quotient, _) = divide_magnitudes(&self.magnitude, &other.magnitude);
+        Some(BigInt::new(self.negative != other.negative, quotient))
+    }
+
+    fn checked_rem(&self, other: &Self) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+        let (_, remainder) = divide_magnitudes(&self.magnitude, &other.magnitude);
+        Some(BigInt::new(self.negative, remainder))
+    }
+
+    fn checked_pow(&self, exponent: &Self) -> Option<Self> {
+        let mut exponent = exponent.to_u64()?;
+        // zero and units stay the same size no matter the exponent
+        if self.bit_length() <= 1 {
+            let negative = self.negative && exponent & 1 == 1;
+            let magnitude = if exponent == 0 {
+                vec![1]
+            } else {
+                self.magnitude.clone()
+            };
+            return Some(BigInt::new(negative, magnitude));
+        }
+        // the result has at most that many bits, refuse to even start if it could get too big
+        let bits = self.bit_length().checked_mul(exponent)?;
+        if bits > MAX_DIGITS as u64 * 32 {
+            return None;
+        }
+        let mut base = self.clone();
+        let mut result = BigInt::from_u8(1);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.checked_mul(&base)?;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = base.checked_mul(&base)?;
+            }
+        }
+        Some(result)
+    }
+
+    fn checked_neg(&self) -> Option<Self> {
+        Some(self.negated())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: [i128; 12] = [
+        0,
+        1,
+        -1,
+        7,
+        -13,
+        4_294_967_295,
+        4_294_967_296,
+        -98_765_432_109_876,
+        i64::MAX as i128,
+        i64::MIN as i128,
+        123_456_789_012_345_678_901_234_567,
+        -1 << 100,
+    ];
+
+    #[test]
+    fn matches_primitive_arithmetic() {
+        for &a in SAMPLES.iter() {
+            for &b in SAMPLES.iter() {
+                let (x, y) = (BigInt::from(a), BigInt::from(b));
+                assert_eq!(a.cmp(&b), x.cmp(&y));
+                assert_eq!(BigInt::from(a + b), x.checked_add(&y).unwrap());
+                assert_eq!(BigInt::from(a - b), x.checked_sub(&y).unwrap());
+                if let Some(product) = a.checked_mul(b) {
+                    assert_eq!(BigInt::from(product), x.checked_mul(&y).unwrap());
+                }
+                if b != 0 {
+                    assert_eq!(BigInt::from(a / b), x.checked_div(&y).unwrap());
+                    assert_eq!(BigInt::from(a % b), x.checked_rem(&y).unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn division_by_zero_is_rejected() {
+        let (x, zero) = (BigInt::from(42i64), BigInt::zero());
+        assert_eq!(None, x.checked_div(&zero));
+        assert_eq!(None, x.checked_rem(&zero));
+        assert_eq!(None, zero.checked_div(&zero));
+    }
+
+    #[test]
+    fn result_size_is_bounded() {
+        let (two, huge) = (BigInt::from(2i64), BigInt::from(99_999_999_999i64));
+        assert_eq!(None, two.checked_pow(&huge));
+        assert_eq!(None, BigInt::from(-3i64).checked_pow(&huge));
+        assert_eq!(None, two.checked_pow(&BigInt::from(u64::MAX)));
+
+        assert_eq!(Some(BigInt::zero()), BigInt::zero().checked_pow(&huge));
+        assert_eq!(
+            Some(BigInt::from(1i64)),
+            BigInt::zero().checked_pow(&BigInt::zero())
+        );
+        assert_eq!(
+            Some(BigInt::from(1i64)),
+            BigInt::from(1i64).checked_pow(&huge)
+        );
+        assert_eq!(
+            Some(BigInt::from(-1i64)),
+            BigInt::from(-1i64).checked_pow(&huge)
+        );
+        assert_eq!(
+            Some(BigInt::from(1i64)),
+            BigInt::from(-1i64).checked_pow(&BigInt::from(10i64))
+        );
+
+        // one bit short of the limit, so that the product bound still allows a factor of one
+        let mut magnitude = vec![u32::MAX; MAX_DIGITS];
+        magnitude[MAX_DIGITS - 1] >>= 1;
+        let largest = BigInt::new(false, magnitude);
+        assert!(largest.checked_mul(&BigInt::from(1i64)).is_some());
+        assert_eq!(None, largest.checked_mul(&two));
+    }
+
+    #[test]
+    fn display() {
+        for &value in SAMPLES.iter() {
+            assert_eq!(value.to_string(), BigInt::from(value).to_string());
+        }
+    }
+
+    #[test]
+    fn exceeding_primitive_range() {
+        let power = BigInt::from(2i64)
+            .checked_pow(&BigInt::from(100i64))
+            .unwrap();
+        assert_eq!("1267650600228229401496703205376", power.to_string());
+
+        let square = power.checked_mul(&power).unwrap();
+        assert_eq!(
+            power,
+            square.checked_div(&power).unwrap(),
+            "{} / {}",
+            square,
+            power
+        );
+        assert_eq!(
+            "-1606938044258990275541962092341162602522202993782792835301376",
+            square.checked_neg().unwrap().to_string()
+        );
+    }
+}
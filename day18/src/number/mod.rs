@@ -0,0 +1,139 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::fmt::{Debug, Display};
+
+pub use bigint::BigInt;
+
+mod bigint;
+
+/// Numeric type the expressions can be evaluated with. All arithmetic is checked,
+/// with `None` signalling that the result does not fit in the type.
+pub trait Number: Clone + Ord + Debug + Display {
+    fn from_u8(value: u8) -> Self;
+
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+
+    /// Truncating division, `None` for a zero divisor.
+    fn checked_div(&self, other: &Self) -> Option<Self>;
+
+    /// Remainder of the truncating division, `None` for a zero divisor.
+    fn checked_rem(&self, other: &Self) -> Option<Self>;
+
+    /// Only ever called with a non-negative exponent.
+    fn checked_pow(&self, exponent: &Self) -> Option<Self>;
+
+    fn checked_neg(&self) -> Option<Self>;
+
+    fn zero() -> Self {
+        Self::from_u8(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+
+    fn is_negative(&self) -> bool {
+        *self < Self::zero()
+    }
+
+    fn checked_abs(&self) -> Option<Self> {
+        if self.is_negative() {
+            self.checked_neg()
+        } else {
+            Some(self.clone())
+        }
+    }
+}
+
+macro_rules! impl_primitive_number {
+    ($($t:ty),*) => {
+        $(
+            impl Number for $t {
+                fn from_u8(value: u8) -> Self {
+                    value.into()
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+
+                fn checked_sub(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_sub(*self, *other)
+                }
+
+                fn checked_mul(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_mul(*self, *other)
+                }
+
+                fn checked_div(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_div(*self, *other)
+                }
+
+                fn checked_rem(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_rem(*self, *other)
+                }
+
+                fn checked_pow(&self, exponent: &Self) -> Option<Self> {
+                    match u32::try_from(*exponent) {
+                        Ok(exponent) => <$t>::checked_pow(*self, exponent),
+                        // only zero and the units don't overflow, and for them just the parity
+                        // of the exponent matters
+                        Err(_) if self.checked_abs().map_or(false, |base| base <= 1) => {
+                            let parity = if *exponent % 2 == 0 { 2 } else { 3 };
+                            <$t>::checked_pow(*self, parity)
+                        }
+                        Err(_) => None,
+                    }
+                }
+
+                fn checked_neg(&self) -> Option<Self> {
+                    <$t>::checked_neg(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_number!(i64, i128, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsigned_numbers_cannot_go_below_zero() {
+        assert_eq!(None, Number::checked_sub(&1u64, &2));
+        assert_eq!(None, Number::checked_neg(&1u64));
+        assert_eq!(Some(0), Number::checked_neg(&0u64));
+        assert!(!Number::is_negative(&0u64));
+    }
+
+    #[test]
+    fn exponents_beyond_u32_overflow() {
+        assert_eq!(Some(1024), Number::checked_pow(&2i128, &10));
+        assert_eq!(None, Number::checked_pow(&2i128, &(1 << 40)));
+
+        // unless the base is zero or a unit
+        assert_eq!(Some(0), Number::checked_pow(&0i128, &(1 << 40)));
+        assert_eq!(Some(1), Number::checked_pow(&1u64, &u64::MAX));
+        assert_eq!(Some(1), Number::checked_pow(&-1i64, &(1 << 40)));
+        assert_eq!(Some(-1), Number::checked_pow(&-1i64, &i64::MAX));
+    }
+}
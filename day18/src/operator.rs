@@ -0,0 +1,190 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::ExpressionError;
+use crate::number::Number;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operator {
+    Addition,
+    Subtraction,
+    Multiplication,
+    Division,
+    Remainder,
+    Power,
+    // unary minus
+    Negation,
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Operator::Addition => write!(f, "+"),
+            Operator::Subtraction | Operator::Negation => write!(f, "-"),
+            Operator::Multiplication => write!(f, "*"),
+            Operator::Division => write!(f, "/"),
+            Operator::Remainder => write!(f, "%"),
+            Operator::Power => write!(f, "^"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
+impl Operator {
+    pub fn is_unary(&self) -> bool {
+        matches!(self, Operator::Negation)
+    }
+
+    fn default_precedence(&self) -> usize {
+        match self {
+            Operator::Addition | Operator::Subtraction => 1,
+            Operator::Multiplication | Operator::Division | Operator::Remainder => 2,
+            Operator::Negation => 3,
+            Operator::Power => 4,
+        }
+    }
+
+    fn default_associativity(&self) -> Associativity {
+        match self {
+            Operator::Power | Operator::Negation => Associativity::Right,
+            _ => Associativity::Left,
+        }
+    }
+
+    pub(crate) fn apply_unary<N: Number>(&self, x: N) -> Result<N, ExpressionError> {
+        match self {
+            Operator::Negation => x.checked_neg().ok_or(ExpressionError::Overflow),
+            _ => panic!(
+                "tried to apply binary operator {} to a single operand",
+                self
+            ),
+        }
+    }
+
+    pub(crate) fn apply<N: Number>(&self, x: N, y: N) -> Result<N, ExpressionError> {
+        let result = match self {
+            Operator::Addition => x.checked_add(&y),
+            Operator::Subtraction => x.checked_sub(&y),
+            Operator::Multiplication => x.checked_mul(&y),
+            Operator::Division | Operator::Remainder if y.is_zero() => {
+                return Err(ExpressionError::DivisionByZero)
+            }
+            Operator::Division => x.checked_div(&y),
+            Operator::Remainder => x.checked_rem(&y),
+            Operator::Power if y.is_negative() => return Err(ExpressionError::NegativeExponent),
+            Operator::Power => x.checked_pow(&y),
+            Operator::Negation => panic!("tried to apply unary operator to two operands"),
+        };
+
+        result.ok_or(ExpressionError::Overflow)
+    }
+}
+
+/// Precedence and associativity of the operators. Higher precedence binds tighter.
+/// Anything not explicitly overridden follows the usual mathematical conventions.
+#[derive(Debug, Clone, Default)]
+pub struct OperatorTable {
+    precedence: HashMap<Operator, usize>,
+    associativity: HashMap<Operator, Associativity>,
+}
+
+impl From<HashMap<Operator, usize>> for OperatorTable {
+    fn from(custom_precedence: HashMap<Operator, usize>) -> Self {
+        OperatorTable {
+            precedence: custom_precedence,
+            associativity: HashMap::new(),
+        }
+    }
+}
+
+impl OperatorTable {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_precedence(mut self, operator: Operator, precedence: usize) -> Self {
+        self.precedence.insert(operator, precedence);
+        self
+    }
+
+    pub fn with_associativity(mut self, operator: Operator, associativity: Associativity) -> Self {
+        self.associativity.insert(operator, associativity);
+        self
+    }
+
+    pub fn precedence(&self, operator: Operator) -> usize {
+        self.precedence
+            .get(&operator)
+            .copied()
+            .unwrap_or_else(|| operator.default_precedence())
+    }
+
+    pub fn associativity(&self, operator: Operator) -> Associativity {
+        self.associativity
+            .get(&operator)
+            .copied()
+            .unwrap_or_else(|| operator.default_associativity())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_arithmetic() {
+        assert_eq!(Ok(-3), Operator::Subtraction.apply(2i64, 5));
+        assert_eq!(Ok(-2), Operator::Division.apply(-7i64, 3));
+        assert_eq!(Ok(-1), Operator::Remainder.apply(-7i64, 3));
+        assert_eq!(Ok(1024), Operator::Power.apply(2i64, 10));
+        assert_eq!(
+            Err(ExpressionError::DivisionByZero),
+            Operator::Remainder.apply(1i64, 0)
+        );
+        assert_eq!(
+            Err(ExpressionError::NegativeExponent),
+            Operator::Power.apply(2i64, -1)
+        );
+        assert_eq!(
+            Err(ExpressionError::Overflow),
+            Operator::Multiplication.apply(i64::MAX, 2)
+        );
+        assert_eq!(
+            Err(ExpressionError::Overflow),
+            Operator::Negation.apply_unary(i64::MIN)
+        );
+    }
+
+    #[test]
+    fn table_overrides_defaults() {
+        let table = OperatorTable::new()
+            .with_precedence(Operator::Addition, 5)
+            .with_associativity(Operator::Subtraction, Associativity::Right);
+
+        assert_eq!(5, table.precedence(Operator::Addition));
+        assert_eq!(2, table.precedence(Operator::Multiplication));
+        assert_eq!(
+            Associativity::Right,
+            table.associativity(Operator::Subtraction)
+        );
+        assert_eq!(Associativity::Left, table.associativity(Operator::Addition));
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::{ParseError, ParseErrorKind, Span};
+use crate::number::Number;
+use crate::operator::Operator;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Token<N> {
+    Number(N),
+    Identifier(String),
+    Operator(Operator),
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+impl<N> Token<N> {
+    // whether a '-' following this token would have to be a unary minus
+    fn expects_operand(previous: Option<&Token<N>>) -> bool {
+        matches!(
+            previous,
+            None | Some(Token::Operator(_)) | Some(Token::LeftParen) | Some(Token::Comma)
+        )
+    }
+}
+
+fn digits_to_number<N: Number>(digits: &[u8]) -> Option<N> {
+    let ten = N::from_u8(10);
+    digits.iter().try_fold(N::zero(), |acc, &digit| {
+        acc.checked_mul(&ten)
+            .and_then(|acc| acc.checked_add(&N::from_u8(digit)))
+    })
+}
+
+pub(crate) fn tokenize<N: Number>(input: &str) -> Result<Vec<(Token<N>, Span)>, ParseError> {
+    let mut tokens: Vec<(Token<N>, Span)> = Vec::new();
+    let mut iter = input.char_indices().peekable();
+
+    while let Some((offset, current)) = iter.next() {
+        let mut end = offset + current.len_utf8();
+        let token = match current {
+            c if c.is_whitespace() => continue,
+            c if c.is_ascii_digit() => {
+                let mut digits = vec![c.to_digit(10).unwrap() as u8];
+                while let Some((index, digit)) = iter
+                    .peek()
+                    .and_then(|&(index, next)| next.to_digit(10).map(|digit| (index, digit)))
+                {
+                    digits.push(digit as u8);
+                    end = index + 1;
+                    iter.next();
+                }
+                let number = digits_to_number(&digits).ok_or_else(|| {
+                    ParseError::new(
+                        ParseErrorKind::NumberTooLarge,
+                        Span::new(offset, end - offset),
+                    )
+                })?;
+                Token::Number(number)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut identifier = c.to_string();
+                while let Some(&(index, next)) = iter.peek() {
+                    if !next.is_alphanumeric() && next != '_' {
+                        break;
+                    }
+                    identifier.push(next);
+                    end = index + next.len_utf8();
+                    iter.next();
+                }
+                Token::Identifier(identifier)
+            }
+            '+' => Token::Operator(Operator::Addition),
+            '-' if Token::expects_operand(tokens.last().map(|(token, _)| token)) => {
+                Token::Operator(Operator::Negation)
+            }
+            '-' => Token::Operator(Operator::Subtraction),
+            '*' => Token::Operator(Operator::Multiplication),
+            '/' => Token::Operator(Operator::Division),
+            '%' => Token::Operator(Operator::Remainder),
+            '^' => Token::Operator(Operator::Power),
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            ',' => Token::Comma,
+            c => {
+                return Err(ParseError::new(
+                    ParseErrorKind::InvalidCharacter(c),
+                    Span::new(offset, end - offset),
+                ))
+            }
+        };
+        tokens.push((token, Span::new(offset, end - offset)))
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits_to_number() {
+        assert_eq!(super::digits_to_number(&[1, 2, 3]), Some(123i64));
+        assert_eq!(super::digits_to_number(&[1]), Some(1i64));
+        assert_eq!(super::digits_to_number::<i64>(&[9; 20]), None);
+        assert_eq!(
+            super::digits_to_number::<i128>(&[9; 20]).map(|n| n + 1),
+            Some(10i128.pow(20))
+        );
+    }
+
+    #[test]
+    fn minus_is_unary_only_in_place_of_an_operand() {
+        assert_eq!(
+            Ok(vec![
+                Token::Operator(Operator::Negation),
+                Token::Number(1),
+                Token::Operator(Operator::Subtraction),
+                Token::LeftParen,
+                Token::Operator(Operator::Negation),
+                Token::Identifier("x_1".to_string()),
+                Token::RightParen,
+            ]),
+            tokenize::<i64>("-1 - (-x_1)")
+                .map(|tokens| tokens.into_iter().map(|(token, _)| token).collect())
+        );
+    }
+
+    #[test]
+    fn invalid_characters_are_rejected() {
+        assert_eq!(
+            Err(ParseError::new(
+                ParseErrorKind::InvalidCharacter('&'),
+                Span::new(2, 1)
+            )),
+            tokenize::<i64>("1 & 2")
+        );
+    }
+
+    #[test]
+    fn tokens_keep_their_spans() {
+        let spans = tokenize::<i64>("max(12,  ab_c)")
+            .unwrap()
+            .into_iter()
+            .map(|(_, span)| (span.offset, span.length))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(0, 3), (3, 1), (4, 2), (6, 1), (9, 4), (13, 1)], spans);
+
+        assert_eq!(
+            Err(ParseError::new(
+                ParseErrorKind::NumberTooLarge,
+                Span::new(4, 20)
+            )),
+            tokenize::<i64>("1 + 99999999999999999999")
+        );
+    }
+}
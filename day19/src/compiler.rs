@@ -0,0 +1,357 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::grammar::{Grammar, Rule};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompileError {
+    /// The grammar refers to the rules from themselves, so it cannot be described
+    /// by a regular expression in general.
+    RecursiveRules(Vec<usize>),
+    UndefinedRule(usize),
+    /// Expanding the rules would produce a pattern or an automaton over the size limit,
+    /// as every use of a rule gets its own copy.
+    TooLarge,
+}
+
+/// Upper bound on the size of the compiled patterns and the number of automaton states.
+const MAX_SIZE: usize = 1 << 20;
+
+// checks whether everything used by the start rule is defined and not recursive
+fn check_compilable(grammar: &Grammar, start: usize) -> Result<(), CompileError> {
+    let reachable = grammar.reachable_rules(vec![start]);
+    if let Some(&undefined) = reachable.iter().find(|&&id| grammar.rule(id).is_none()) {
+        return Err(CompileError::UndefinedRule(undefined));
+    }
+
+    let recursive: Vec<_> = grammar
+        .recursive_rules()
+        .intersection(&reachable)
+        .copied()
+        .collect();
+    if !recursive.is_empty() {
+        return Err(CompileError::RecursiveRules(recursive));
+    }
+
+    Ok(())
+}
+
+// size of the rule with all of its references expanded, which bounds both the length
+// of its pattern and the number of its automaton states (up to a constant factor)
+fn expanded_size(grammar: &Grammar, id: usize, memo: &mut HashMap<usize, usize>) -> usize {
+    if let Some(&size) = memo.get(&id) {
+        return size;
+    }
+
+    let size = match grammar.rule(id).unwrap() {
+        Rule::Terminal(terminal) => terminal.len().max(1),
+        Rule::Nonterminal(rule) => rule.subrules.iter().fold(0usize, |size, subrule| {
+            subrule
+                .iter()
+                .map(|&id| expanded_size(grammar, id, memo))
+                .fold(size.saturating_add(1), usize::saturating_add)
+        }),
+    };
+
+    memo.insert(id, size);
+    size
+}
+
+fn check_size(grammar: &Grammar, start: usize) -> Result<(), CompileError> {
+    if expanded_size(grammar, start, &mut HashMap::new()) > MAX_SIZE {
+        Err(CompileError::TooLarge)
+    } else {
+        Ok(())
+    }
+}
+
+// characters with special meaning in the `regex` syntax
+const REGEX_META_CHARACTERS: &str = r"\.+*?()|[]{}^$#&-~";
+
+fn escape(terminal: &str) -> String {
+    let mut escaped = String::with_capacity(terminal.len());
+    for c in terminal.chars() {
+        if REGEX_META_CHARACTERS.contains(c) {
+            escaped.push('\\')
+        }
+        escaped.push(c)
+    }
+    escaped
+}
+
+fn rule_regex(grammar: &Grammar, id: usize, memo: &mut HashMap<usize, String>) -> String {
+    if let Some(regex) = memo.get(&id) {
+        return regex.clone();
+    }
+
+    // rules with alternatives are always grouped, so they can be safely concatenated
+    let regex = match grammar.rule(id).unwrap() {
+        Rule::Terminal(terminal) => escape(terminal),
+        Rule::Nonterminal(rule) => {
+            let alternatives: Vec<String> = rule
+                .subrules
+                .iter()
+                .map(|subrule| {
+                    subrule
+                        .iter()
+                        .map(|&id| rule_regex(grammar, id, memo))
+                        .collect()
+                })
+                .collect();
+            match alternatives.as_slice() {
+                [single] => single.clone(),
+                _ => format!("(?:{})", alternatives.join("|")),
+            }
+        }
+    };
+
+    memo.insert(id, regex.clone());
+    regex
+}
+
+/// Deterministic automaton accepting exactly the words derived from a rule of the grammar.
+#[derive(Debug, Clone)]
+pub struct Dfa {
+    transitions: Vec<HashMap<char, usize>>,
+    accepting: Vec<bool>,
+}
+
+impl Dfa {
+    const START: usize = 0;
+
+    pub fn num_states(&self) -> usize {
+        self.transitions.len()
+    }
+
+    pub fn matches(&self, word: &str) -> bool {
+        let mut state = Self::START;
+        for c in word.chars() {
+            match self.transitions[state].get(&c) {
+                Some(&next) => state = next,
+                None => return false,
+            }
+        }
+        self.accepting[state]
+    }
+}
+
+#[derive(Default)]
+struct Nfa {
+    epsilon: Vec<Vec<usize>>,
+    transitions: Vec<Vec<(char, usize)>>,
+}
+
+impl Nfa {
+    fn new_state(&mut self) -> usize {
+        self.epsilon.push(Vec::new());
+        self.transitions.push(Vec::new());
+        self.epsilon.len() - 1
+    }
+
+    // connects `from` with `to` through states matching the sequence of rules
+    fn add_sequence(&mut self, grammar: &Grammar, rules: &[usize], from: usize, to: usize) {
+        if rules.is_empty() {
+            self.epsilon[from].push(to);
+            return;
+        }
+
+        let mut current = from;
+        for (i, &id) in rules.iter().enumerate() {
+            let next = if i == rules.len() - 1 {
+                to
+            } else {
+                self.new_state()
+            };
+            self.add_rule(grammar, id, current, next);
+            current = next;
+        }
+    }
+
+    // the grammar is not recursive, so every use of a rule can get its own copy of states
+    fn add_rule(&mut self, grammar: &Grammar, id: usize, from: usize, to: usize) {
+        match grammar.rule(id).unwrap() {
+            Rule::Terminal(terminal) if terminal.is_empty() => self.epsilon[from].push(to),
+            Rule::Terminal(terminal) => {
+                let chars: Vec<_> = terminal.chars().collect();
+                let mut current = from;
+                for (i, &c) in chars.iter().enumerate() {
+                    let next = if i == chars.len() - 1 {
+                        to
+                    } else {
+                        self.new_state()
+                    };
+                    self.transitions[current].push((c, next));
+                    current = next;
+                }
+            }
+            Rule::Nonterminal(rule) => {
+                for subrule in rule.subrules.iter() {
+                    self.add_sequence(grammar, subrule, from, to)
+                }
+            }
+        }
+    }
+
+    fn closure(&self, states: BTreeSet<usize>) -> BTreeSet<usize> {
+        let mut closure = BTreeSet::new();
+        let mut stack: Vec<_> = states.into_iter().collect();
+        while let Some(state) = stack.pop() {
+            if closure.insert(state) {
+                stack.extend(self.epsilon[state].iter().copied())
+            }
+        }
+        closure
+    }
+
+    // subset construction, which might still need exponentially many states
+    fn into_dfa(self, start: usize, accepting: usize) -> Result<Dfa, CompileError> {
+        let initial = self.closure(vec![start].into_iter().collect());
+        let mut ids = HashMap::new();
+        ids.insert(initial.clone(), Dfa::START);
+        let mut subsets = vec![initial];
+        let mut transitions = Vec::new();
+
+        while transitions.len() < subsets.len() {
+            if subsets.len() > MAX_SIZE {
+                return Err(CompileError::TooLarge);
+            }
+            let mut targets: BTreeMap<char, BTreeSet<usize>> = BTreeMap::new();
+            for &state in subsets[transitions.len()].iter() {
+                for &(c, next) in self.transitions[state].iter() {
+                    targets.entry(c).or_default().insert(next);
+                }
+            }
+
+            let mut state_transitions = HashMap::new();
+            for (c, target) in targets {
+                let target = self.closure(target);
+                let id = *ids.entry(target.clone()).or_insert_with(|| {
+                    subsets.push(target);
+                    subsets.len() - 1
+                });
+                state_transitions.insert(c, id);
+            }
+            transitions.push(state_transitions);
+        }
+
+        Ok(Dfa {
+            transitions,
+            accepting: subsets
+                .iter()
+                .map(|subset| subset.contains(&accepting))
+                .collect(),
+        })
+    }
+}
+
+impl Grammar {
+    /// Compiles the rule into a `regex` compatible pattern matching the entire word.
+    pub fn to_regex(&self, start: usize) -> Result<String, CompileError> {
+        check_compilable(self, start)?;
+        check_size(self, start)?;
+        Ok(format!(
+            "^{}$",
+            rule_regex(self, start, &mut HashMap::new())
+        ))
+    }
+
+    /// Compiles the rule into an automaton matching words in linear time.
+    pub fn to_dfa(&self, start: usize) -> Result<Dfa, CompileError> {
+        check_compilable(self, start)?;
+        check_size(self, start)?;
+
+        let mut nfa = Nfa::default();
+        let (initial, accepting) = (nfa.new_state(), nfa.new_state());
+        nfa.add_rule(self, start, initial, accepting);
+        nfa.into_dfa(initial, accepting)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_grammar() -> Grammar {
+        Grammar::from("0: 4 1 5\n1: 2 3 | 3 2\n2: 4 4 | 5 5\n3: 4 5 | 5 4\n4: \"a\"\n5: \"b\"")
+    }
+
+    #[test]
+    fn regex() {
+        let grammar = Grammar::from("0: 1 2\n1: \"a\"\n2: 1 3 | 3 1\n3: \"b\"");
+        assert_eq!(Ok("^a(?:ab|ba)$".to_string()), grammar.to_regex(0));
+
+        let grammar = Grammar::from("0: 1 2 | 2\n1: \"a+\"\n2: 3 |\n3: \"(b)\"");
+        assert_eq!(
+            Ok(r"^(?:a\+(?:\(b\)|)|(?:\(b\)|))$".to_string()),
+            grammar.to_regex(0)
+        );
+    }
+
+    #[test]
+    fn dfa_agrees_with_the_recognizer() {
+        let grammar = sample_grammar();
+        let dfa = grammar.to_dfa(0).unwrap();
+        for length in 0..=7 {
+            for bits in 0..1 << length {
+                let word: String = (0..length)
+                    .map(|i| if bits >> i & 1 == 1 { 'b' } else { 'a' })
+                    .collect();
+                assert_eq!(grammar.check_word(&word), dfa.matches(&word), "{}", word);
+            }
+        }
+    }
+
+    #[test]
+    fn multi_character_and_empty_terminals() {
+        let grammar = Grammar::from("0: 1 2 1\n1: 4 | 3\n2: \"-\"\n3: \"\"\n4: \"ab\"");
+        let dfa = grammar.to_dfa(0).unwrap();
+        for word in &["ab-ab", "-ab", "ab-", "-"] {
+            assert!(dfa.matches(word), "{}", word);
+        }
+        for word in &["a-b", "abab", "", "ab-a"] {
+            assert!(!dfa.matches(word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn exponential_expansions_are_rejected() {
+        // every rule uses the next one twice, so rule 0 expands to 2^40 terminals
+        let mut rules: Vec<_> = (0..40)
+            .map(|id| format!("{}: {} {}", id, id + 1, id + 1))
+            .collect();
+        rules.push("40: \"a\"".to_string());
+        let grammar = Grammar::from(rules.join("\n").as_str());
+
+        assert_eq!(Err(CompileError::TooLarge), grammar.to_regex(0));
+        assert_eq!(Err(CompileError::TooLarge), grammar.to_dfa(0).map(|_| ()));
+        assert_eq!(Ok("^aaaa$".to_string()), grammar.to_regex(38));
+    }
+
+    #[test]
+    fn uncompilable_grammars() {
+        let grammar = Grammar::from("0: 1 2\n1: \"a\"\n2: 1 | 1 2\n3: 3");
+        assert_eq!(
+            Err(CompileError::RecursiveRules(vec![2])),
+            grammar.to_regex(0).map(|_| ())
+        );
+        assert!(grammar.to_dfa(1).is_ok());
+
+        let grammar = Grammar::from("0: 1 7\n1: \"a\"");
+        assert_eq!(
+            Err(CompileError::UndefinedRule(7)),
+            grammar.to_dfa(0).map(|_| ())
+        );
+    }
+}
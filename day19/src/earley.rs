@@ -0,0 +1,751 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::grammar::{Grammar, Rule};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ops::{Add, Mul};
+
+/// Number of distinct parse trees of a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DerivationCount {
+    /// Saturates at `u128::MAX` for extremely ambiguous grammars.
+    Finite(u128),
+    /// The word can be derived through a cycle, such as `0: 0 | "a"`.
+    Infinite,
+}
+
+impl Add for DerivationCount {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        match (self, other) {
+            (DerivationCount::Finite(a), DerivationCount::Finite(b)) => {
+                DerivationCount::Finite(a.saturating_add(b))
+            }
+            _ => DerivationCount::Infinite,
+        }
+    }
+}
+
+impl Mul for DerivationCount {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        match (self, other) {
+            (DerivationCount::Finite(a), DerivationCount::Finite(b)) => {
+                DerivationCount::Finite(a.saturating_mul(b))
+            }
+            (DerivationCount::Finite(0), _) | (_, DerivationCount::Finite(0)) => {
+                DerivationCount::Finite(0)
+            }
+            _ => DerivationCount::Infinite,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTree {
+    Terminal {
+        rule: usize,
+        text: String,
+    },
+    Node {
+        rule: usize,
+        /// Index of the subrule that got used.
+        alternative: usize,
+        children: Vec<ParseTree>,
+    },
+}
+
+impl ParseTree {
+    pub fn rule(&self) -> usize {
+        match self {
+            ParseTree::Terminal { rule, .. } | ParseTree::Node { rule, .. } => *rule,
+        }
+    }
+
+    /// The part of the word that is covered by the tree.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        let mut stack = vec![self];
+        while let Some(tree) = stack.pop() {
+            match tree {
+                ParseTree::Terminal { text: terminal, .. } => text.push_str(terminal),
+                ParseTree::Node { children, .. } => stack.extend(children.iter().rev()),
+            }
+        }
+        text
+    }
+}
+
+// a partially matched subrule of a nonterminal rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Item {
+    rule: usize,
+    alternative: usize,
+    // number of subrule symbols matched so far
+    dot: usize,
+    // position in the word at which the match started
+    origin: usize,
+}
+
+impl Item {
+    fn advanced(self) -> Self {
+        Item {
+            dot: self.dot + 1,
+            ..self
+        }
+    }
+}
+
+struct ChartBuilder<'a> {
+    grammar: &'a Grammar,
+    word: &'a str,
+    nullable: Vec<bool>,
+    sets: Vec<Vec<Item>>,
+    seen: Vec<HashSet<Item>>,
+    // items of every set indexed by the rule they are expecting next
+    waiting: Vec<HashMap<usize, Vec<Item>>>,
+    completed: Vec<HashMap<usize, BTreeSet<usize>>>,
+}
+
+impl<'a> ChartBuilder<'a> {
+    fn add(&mut self, position: usize, item: Item) {
+        if self.seen[position].insert(item) {
+            if let Some(next) = self.next_symbol(&item) {
+                self.waiting[position].entry(next).or_default().push(item)
+            }
+            self.sets[position].push(item)
+        }
+    }
+
+    // returns whether the span is new
+    fn complete(&mut self, rule: usize, start: usize, end: usize) -> bool {
+        self.completed[start].entry(rule).or_default().insert(end)
+    }
+
+    fn next_symbol(&self, item: &Item) -> Option<usize> {
+        match self.grammar.rule(item.rule) {
+            Some(Rule::Nonterminal(rule)) => rule.subrules[item.alternative].get(item.dot).copied(),
+            _ => None,
+        }
+    }
+
+    // starts matching the rule at given position on behalf of the parent item
+    fn predict(&mut self, rule: usize, position: usize, parent: Option<Item>) {
+        let grammar = self.grammar;
+        match grammar.rule(rule) {
+            Some(Rule::Terminal(terminal))
+                if self.word[position..].starts_with(terminal.as_str()) =>
+            {
+                let end = position + terminal.len();
+                self.complete(rule, position, end);
+                if let Some(parent) = parent {
+                    self.add(end, parent.advanced())
+                }
+            }
+            // undefined rules do not match anything
+            None | Some(Rule::Terminal(_)) => (),
+            Some(Rule::Nonterminal(nonterminal)) => {
+                for alternative in 0..nonterminal.subrules.len() {
+                    self.add(
+                        position,
+                        Item {
+                            rule,
+                            alternative,
+                            dot: 0,
+                            origin: position,
+                        },
+                    )
+                }
+                // the rule might complete without consuming anything, after the parent has
+                // already been looked at (Aycock and Horspool)
+                if self.nullable[rule] {
+                    if let Some(parent) = parent {
+                        self.add(position, parent.advanced())
+                    }
+                }
+            }
+        }
+    }
+
+    fn build(mut self, start: usize) -> Chart<'a> {
+        self.predict(start, 0, None);
+
+        for position in 0..self.sets.len() {
+            let mut index = 0;
+            while index < self.sets[position].len() {
+                let item = self.sets[position][index];
+                index += 1;
+
+                match self.next_symbol(&item) {
+                    Some(next) => self.predict(next, position, Some(item)),
+                    // once a span is completed, all items waiting for it have been advanced
+                    None if self.complete(item.rule, item.origin, position) => {
+                        let waiting = self.waiting[item.origin]
+                            .get(&item.rule)
+                            .cloned()
+                            .unwrap_or_default();
+                        for parent in waiting {
+                            self.add(position, parent.advanced())
+                        }
+                    }
+                    None => (),
+                }
+            }
+        }
+
+        Chart {
+            grammar: self.grammar,
+            word: self.word,
+            completed: self.completed,
+        }
+    }
+}
+
+/// Result of running the Earley parser - all the spans of the word matched by the rules
+/// that could have been used while deriving it.
+struct Chart<'a> {
+    grammar: &'a Grammar,
+    word: &'a str,
+    // for every start position, end positions of spans matched by each rule
+    completed: Vec<HashMap<usize, BTreeSet<usize>>>,
+}
+
+type Span = (usize, usize, usize);
+
+// something to be derived from a span of the word
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+enum Goal {
+    // the span derived by the rule
+    Rule(Span),
+    // the span derived by the symbols of an alternative of the rule, starting at the dot
+    Sequence {
+        rule: usize,
+        alternative: usize,
+        dot: usize,
+        start: usize,
+        end: usize,
+    },
+}
+
+struct CountFrame {
+    goal: Goal,
+    // possible ends of the first symbol of a sequence
+    ends: Vec<usize>,
+    step: usize,
+    // count of the remainder of a sequence after the current end of its first symbol
+    remaining: Option<DerivationCount>,
+    total: DerivationCount,
+}
+
+enum TreeFrame {
+    Rule {
+        span: Span,
+        alternative: usize,
+    },
+    Sequence {
+        rule: usize,
+        alternative: usize,
+        dot: usize,
+        start: usize,
+        end: usize,
+        first: usize,
+        ends: Vec<usize>,
+        index: usize,
+        // trees of the remainder of the sequence, once it has been matched
+        remaining: Option<Vec<ParseTree>>,
+    },
+}
+
+enum Step {
+    // look for the trees of the goal first
+    Enter(Goal),
+    // the frame is done, with the trees it found if any
+    Finish(Option<Vec<ParseTree>>),
+}
+
+impl<'a> Chart<'a> {
+    fn new(grammar: &'a Grammar, word: &'a str, start: usize) -> Self {
+        let positions = word.len() + 1;
+        ChartBuilder {
+            grammar,
+            word,
+            nullable: grammar.nullable_rules(),
+            sets: vec![Vec::new(); positions],
+            seen: vec![HashSet::new(); positions],
+            waiting: vec![HashMap::new(); positions],
+            completed: vec![HashMap::new(); positions],
+        }
+        .build(start)
+    }
+
+    fn ends(&self, rule: usize, start: usize, max_end: usize) -> Vec<usize> {
+        self.completed[start]
+            .get(&rule)
+            .map(|ends| ends.range(..=max_end).copied().collect())
+            .unwrap_or_default()
+    }
+
+    fn matches(&self, rule: usize) -> bool {
+        self.completed[0]
+            .get(&rule)
+            .map(|ends| ends.contains(&self.word.len()))
+            .unwrap_or(false)
+    }
+
+    fn alternatives(&self, rule: usize) -> usize {
+        match self.grammar.rule(rule) {
+            Some(Rule::Nonterminal(nonterminal)) => nonterminal.subrules.len(),
+            _ => 0,
+        }
+    }
+
+    fn symbols(&self, rule: usize, alternative: usize) -> &'a [usize] {
+        match self.grammar.rule(rule) {
+            Some(Rule::Nonterminal(nonterminal)) => &nonterminal.subrules[alternative],
+            _ => &[],
+        }
+    }
+
+    // both the counting and the tree building keep their own stacks rather than recursing,
+    // as the derivations of long words can get arbitrarily deep
+
+    fn count_frame(
+        &self,
+        goal: Goal,
+        memo: &mut HashMap<Span, Option<DerivationCount>>,
+    ) -> CountFrame {
+        let ends = match goal {
+            Goal::Rule(span) => {
+                memo.insert(span, None);
+                Vec::new()
+            }
+            Goal::Sequence {
+                rule,
+                alternative,
+                dot,
+                start,
+                end,
+            } => match self.symbols(rule, alternative).get(dot) {
+                Some(&first) => self.ends(first, start, end),
+                None => Vec::new(),
+            },
+        };
+        CountFrame {
+            goal,
+            ends,
+            step: 0,
+            remaining: None,
+            total: DerivationCount::Finite(0),
+        }
+    }
+
+    // advances the frame as far as possible, given the count of the sequence it was waiting for,
+    // returning either its final count or the goal that has to be counted first
+    fn advance_count(
+        &self,
+        frame: &mut CountFrame,
+        returned: Option<DerivationCount>,
+        memo: &HashMap<Span, Option<DerivationCount>>,
+    ) -> Result<DerivationCount, Goal> {
+        match frame.goal {
+            Goal::Rule((rule, start, end)) => {
+                let alternatives = match self.grammar.rule(rule) {
+                    Some(Rule::Terminal(_)) => return Ok(DerivationCount::Finite(1)),
+                    Some(Rule::Nonterminal(nonterminal)) => nonterminal.subrules.len(),
+                    None => return Ok(DerivationCount::Finite(0)),
+                };
+                if let Some(count) = returned {
+                    frame.total = frame.total + count;
+                    frame.step += 1;
+                }
+                if frame.step < alternatives {
+                    return Err(Goal::Sequence {
+                        rule,
+                        alternative: frame.step,
+                        dot: 0,
+                        start,
+                        end,
+                    });
+                }
+            }
+            Goal::Sequence {
+                rule,
+                alternative,
+                dot,
+                start,
+                end,
+            } => {
+                let first = match self.symbols(rule, alternative).get(dot) {
+                    Some(&first) => first,
+                    None if start == end => return Ok(DerivationCount::Finite(1)),
+                    None => return Ok(DerivationCount::Finite(0)),
+                };
+                // the remainder is counted first, so that a cycle is only reported if it can
+                // actually be a part of a complete derivation
+                // only the counts of the remainders are handed back, the rules are read from the memo
+                let mut returned = returned;
+                while let Some(&middle) = frame.ends.get(frame.step) {
+                    let remaining = match frame.remaining.or(returned.take()) {
+                        Some(remaining) => remaining,
+                        None => {
+                            return Err(Goal::Sequence {
+                                rule,
+                                alternative,
+                                dot: dot + 1,
+                                start: middle,
+                                end,
+                            })
+                        }
+                    };
+                    frame.remaining = Some(remaining);
+                    if remaining != DerivationCount::Finite(0) {
+                        let span = (first, start, middle);
+                        let count = match memo.get(&span) {
+                            Some(Some(count)) => *count,
+                            // the span is derived from itself
+                            Some(None) => DerivationCount::Infinite,
+                            None => return Err(Goal::Rule(span)),
+                        };
+                        frame.total = frame.total + count * remaining;
+                    }
+                    frame.remaining = None;
+                    frame.step += 1;
+                }
+            }
+        }
+        Ok(frame.total)
+    }
+
+    // sequences are counted anew every time, as there are too many of them to be worth keeping,
+    // while the counts of the rules are memoized
+    fn count(&self, rule: usize, start: usize, end: usize) -> DerivationCount {
+        let mut memo = HashMap::new();
+        let mut stack = vec![self.count_frame(Goal::Rule((rule, start, end)), &mut memo)];
+        let mut returned = None;
+
+        while let Some(frame) = stack.last_mut() {
+            match self.advance_count(frame, returned.take(), &memo) {
+                Ok(count) => {
+                    if let Goal::Rule(span) = frame.goal {
+                        memo.insert(span, Some(count));
+                    }
+                    stack.pop();
+                    returned = Some(count);
+                }
+                Err(goal) => {
+                    let frame = self.count_frame(goal, &mut memo);
+                    stack.push(frame)
+                }
+            }
+        }
+
+        returned.expect("the root is always counted")
+    }
+
+    // starts looking for the trees of the goal, unless they are known right away
+    fn enter_tree(
+        &self,
+        goal: Goal,
+        stack: &mut Vec<TreeFrame>,
+        visiting: &mut HashSet<Span>,
+    ) -> Option<Option<Vec<ParseTree>>> {
+        match goal {
+            Goal::Rule(span) => {
+                // the smallest tree never needs to derive a span from itself
+                if !visiting.insert(span) {
+                    return Some(None);
+                }
+                let rule = span.0;
+                match self.grammar.rule(rule) {
+                    Some(Rule::Terminal(text)) => {
+                        visiting.remove(&span);
+                        Some(Some(vec![ParseTree::Terminal {
+                            rule,
+                            text: text.clone(),
+                        }]))
+                    }
+                    Some(Rule::Nonterminal(_)) => {
+                        stack.push(TreeFrame::Rule {
+                            span,
+                            alternative: 0,
+                        });
+                        None
+                    }
+                    None => {
+                        visiting.remove(&span);
+                        Some(None)
+                    }
+                }
+            }
+            Goal::Sequence {
+                rule,
+                alternative,
+                dot,
+                start,
+                end,
+            } => match self.symbols(rule, alternative).get(dot) {
+                Some(&first) => {
+                    stack.push(TreeFrame::Sequence {
+                        rule,
+                        alternative,
+                        dot,
+                        start,
+                        end,
+                        first,
+                        ends: self.ends(first, start, end),
+                        index: 0,
+                        remaining: None,
+                    });
+                    None
+                }
+                None if start == end => Some(Some(Vec::new())),
+                None => Some(None),
+            },
+        }
+    }
+
+    // trees are looked for depth first, trying the alternatives and the ends of the first symbols
+    // of the sequences in order, with the remainder of a sequence being matched before its first
+    // symbol; every finished goal hands back its trees (if any) to the frame below it
+    fn tree(&self, rule: usize, start: usize, end: usize) -> Option<ParseTree> {
+        let mut stack = Vec::new();
+        let mut visiting = HashSet::new();
+        let mut returned =
+            self.enter_tree(Goal::Rule((rule, start, end)), &mut stack, &mut visiting);
+
+        while let Some(frame) = stack.last_mut() {
+            let step = match frame {
+                TreeFrame::Rule { span, alternative } => {
+                    let (rule, start, end) = *span;
+                    match returned.take() {
+                        Some(Some(children)) => Step::Finish(Some(vec![ParseTree::Node {
+                            rule,
+                            alternative: *alternative,
+                            children,
+                        }])),
+                        found => {
+                            if found.is_some() {
+                                *alternative += 1;
+                            }
+                            if *alternative < self.alternatives(rule) {
+                                Step::Enter(Goal::Sequence {
+                                    rule,
+                                    alternative: *alternative,
+                                    dot: 0,
+                                    start,
+                                    end,
+                                })
+                            } else {
+                                Step::Finish(None)
+                            }
+                        }
+                    }
+                }
+                TreeFrame::Sequence {
+                    rule,
+                    alternative,
+                    dot,
+                    start,
+                    end,
+                    first,
+                    ends,
+                    index,
+                    remaining,
+                } => match (returned.take(), remaining.take()) {
+                    // the remainder matched, so the first symbol has to match up to it
+                    (Some(Some(rest)), None) => {
+                        *remaining = Some(rest);
+                        Step::Enter(Goal::Rule((*first, *start, ends[*index])))
+                    }
+                    (Some(Some(mut trees)), Some(rest)) => {
+                        trees.extend(rest);
+                        Step::Finish(Some(trees))
+                    }
+                    (found, _) => {
+                        if found.is_some() {
+                            *index += 1;
+                        }
+                        match ends.get(*index) {
+                            Some(&middle) => Step::Enter(Goal::Sequence {
+                                rule: *rule,
+                                alternative: *alternative,
+                                dot: *dot + 1,
+                                start: middle,
+                                end: *end,
+                            }),
+                            None => Step::Finish(None),
+                        }
+                    }
+                },
+            };
+            returned = match step {
+                Step::Enter(goal) => self.enter_tree(goal, &mut stack, &mut visiting),
+                Step::Finish(trees) => {
+                    if let Some(TreeFrame::Rule { span, .. }) = stack.pop() {
+                        visiting.remove(&span);
+                    }
+                    Some(trees)
+                }
+            };
+        }
+
+        returned.flatten().and_then(|mut trees| trees.pop())
+    }
+}
+
+impl Grammar {
+    /// Checks whether the word can be derived from the start rule.
+    pub fn recognize(&self, word: &str, start: usize) -> bool {
+        Chart::new(self, word, start).matches(start)
+    }
+
+    /// Finds a parse tree of the word derived from the start rule.
+    /// For ambiguous words, alternatives listed earlier in the rules are preferred.
+    pub fn parse(&self, word: &str, start: usize) -> Option<ParseTree> {
+        let chart = Chart::new(self, word, start);
+        if !chart.matches(start) {
+            return None;
+        }
+        chart.tree(start, 0, word.len())
+    }
+
+    /// Counts the distinct parse trees of the word derived from the start rule.
+    pub fn count_derivations(&self, word: &str, start: usize) -> DerivationCount {
+        let chart = Chart::new(self, word, start);
+        if !chart.matches(start) {
+            return DerivationCount::Finite(0);
+        }
+        chart.count(start, 0, word.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn left_recursion() {
+        // 0: 0 "+" 1 | 1
+        let grammar = Grammar::from("0: 0 2 1 | 1\n1: \"x\"\n2: \"+\"");
+        assert!(grammar.recognize("x+x+x", 0));
+        assert!(!grammar.recognize("x+", 0));
+        assert_eq!(
+            DerivationCount::Finite(1),
+            grammar.count_derivations("x+x+x", 0)
+        );
+    }
+
+    #[test]
+    fn empty_productions_and_long_terminals() {
+        // balanced "ab" pairs wrapped around optional "--"
+        let grammar = Grammar::from("0: 1 0 2 | 3\n1: \"ab\"\n2: \"ba\"\n3: 4 |\n4: \"--\"");
+        for word in &["", "--", "abba", "ab--ba", "ababbaba"] {
+            assert!(grammar.recognize(word, 0), "{}", word);
+        }
+        for word in &["a", "ab", "abab", "-", "abb-a"] {
+            assert!(!grammar.recognize(word, 0), "{}", word);
+        }
+    }
+
+    #[test]
+    fn any_start_rule() {
+        let grammar = Grammar::from("0: 1 2\n1: \"a\"\n2: 3 | 1\n3: \"b\"");
+        assert!(grammar.recognize("b", 2));
+        assert!(grammar.recognize("a", 2));
+        assert!(grammar.recognize("a", 1));
+        assert!(!grammar.recognize("ab", 2));
+        assert!(!grammar.recognize("a", 4));
+    }
+
+    #[test]
+    fn derivation_counts() {
+        // 0: 0 0 | "a" has Catalan number of derivations
+        let grammar = Grammar::from("0: 0 0 | 1\n1: \"a\"");
+        let counts: Vec<_> = (1..=6)
+            .map(|n| grammar.count_derivations(&"a".repeat(n), 0))
+            .collect();
+        let catalan = [1, 1, 2, 5, 14, 42];
+        assert_eq!(
+            catalan
+                .iter()
+                .map(|&n| DerivationCount::Finite(n))
+                .collect::<Vec<_>>(),
+            counts
+        );
+        assert_eq!(
+            DerivationCount::Finite(0),
+            grammar.count_derivations("b", 0)
+        );
+
+        let cyclic = Grammar::from("0: 0 | 1 2\n1: \"a\"\n2:");
+        assert_eq!(DerivationCount::Infinite, cyclic.count_derivations("a", 0));
+    }
+
+    #[test]
+    fn parse_trees() {
+        let grammar = Grammar::from("0: 1 0 | \n1: \"ab\"");
+        let tree = grammar.parse("abab", 0).unwrap();
+        assert_eq!("abab", tree.text());
+        assert_eq!(0, tree.rule());
+        assert_eq!(
+            ParseTree::Node {
+                rule: 0,
+                alternative: 0,
+                children: vec![
+                    ParseTree::Terminal {
+                        rule: 1,
+                        text: "ab".to_string()
+                    },
+                    ParseTree::Node {
+                        rule: 0,
+                        alternative: 0,
+                        children: vec![
+                            ParseTree::Terminal {
+                                rule: 1,
+                                text: "ab".to_string()
+                            },
+                            ParseTree::Node {
+                                rule: 0,
+                                alternative: 1,
+                                children: vec![]
+                            }
+                        ]
+                    }
+                ]
+            },
+            tree
+        );
+        assert_eq!(None, grammar.parse("aba", 0));
+
+        // cyclic derivations still have a finite tree
+        let cyclic = Grammar::from("0: 0 | 1\n1: \"a\"");
+        assert_eq!("a", cyclic.parse("a", 0).unwrap().text());
+    }
+
+    #[test]
+    fn long_words_do_not_overflow_the_stack() {
+        // deep enough for the derivations to overflow the stack when walked recursively
+        let word = "a".repeat(1_500);
+        for grammar in &["0: 1 0 | 1\n1: \"a\"", "0: 0 1 | 1\n1: \"a\""] {
+            let grammar = Grammar::from(*grammar);
+            assert_eq!(
+                DerivationCount::Finite(1),
+                grammar.count_derivations(&word, 0)
+            );
+            assert_eq!(word, grammar.parse(&word, 0).unwrap().text());
+        }
+    }
+}
@@ -0,0 +1,454 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::earley::DerivationCount;
+use crate::grammar::{Grammar, Rule};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::{BTreeSet, HashSet};
+
+/// Reasons for the derivations of a grammar not being countable, in which case they cannot
+/// be drawn uniformly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneratorError {
+    /// Some words are derived in infinitely many ways, for example through `0: 0 | 1`.
+    InfiniteDerivations,
+    /// There are more derivations of words short enough than fit in a `u128`.
+    TooManyDerivations,
+}
+
+// concatenates every word of the first set with every word of the second one
+fn concatenate(
+    prefixes: &BTreeSet<String>,
+    suffixes: &BTreeSet<String>,
+    max_length: usize,
+) -> BTreeSet<String> {
+    let mut words = BTreeSet::new();
+    for prefix in prefixes {
+        let prefix_length = prefix.chars().count();
+        for suffix in suffixes {
+            if prefix_length + suffix.chars().count() <= max_length {
+                words.insert(format!("{}{}", prefix, suffix));
+            }
+        }
+    }
+    words
+}
+
+// convolution of the numbers of derivations of consecutive parts of a word;
+// the arithmetic saturates, and as the counts never decrease, a count is exact
+// unless it ends up saturated itself
+fn concatenate_counts(prefixes: &[u128], suffixes: &[u128]) -> Vec<u128> {
+    let mut counts = vec![0u128; prefixes.len()];
+    for (i, &prefix) in prefixes.iter().enumerate().filter(|(_, &count)| count > 0) {
+        for (j, &suffix) in suffixes.iter().take(prefixes.len() - i).enumerate() {
+            counts[i + j] = counts[i + j].saturating_add(prefix.saturating_mul(suffix));
+        }
+    }
+    counts
+}
+
+// the weights have to have a non-zero sum that fits in a u128
+fn choose_weighted<R: Rng>(weights: &[u128], rng: &mut R) -> usize {
+    let total: u128 = weights.iter().sum();
+    let mut target = rng.gen_range(0..total);
+    for (i, &weight) in weights.iter().enumerate() {
+        if target < weight {
+            return i;
+        }
+        target -= weight;
+    }
+    unreachable!("the target is always below the total weight")
+}
+
+impl Grammar {
+    /// All words of at most `max_length` characters derived from the start rule.
+    pub fn enumerate_words(&self, start: usize, max_length: usize) -> BTreeSet<String> {
+        let mut words = vec![BTreeSet::new(); self.rules.len()];
+
+        // the sets only ever grow and are bounded by the length, so this always terminates
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for id in self.rule_ids() {
+                let derived = match self.rule(id).unwrap() {
+                    Rule::Terminal(terminal) if terminal.chars().count() <= max_length => {
+                        vec![terminal.clone()].into_iter().collect()
+                    }
+                    Rule::Terminal(_) => BTreeSet::new(),
+                    Rule::Nonterminal(rule) => {
+                        let mut derived = BTreeSet::new();
+                        for subrule in rule.subrules.iter() {
+                            let mut sequence: BTreeSet<_> =
+                                vec![String::new()].into_iter().collect();
+                            for &part in subrule.iter() {
+                                let empty = BTreeSet::new();
+                                let part_words = words.get(part).unwrap_or(&empty);
+                                sequence = concatenate(&sequence, part_words, max_length);
+                            }
+                            derived.append(&mut sequence);
+                        }
+                        derived
+                    }
+                };
+                if derived.len() > words[id].len() {
+                    words[id] = derived;
+                    changed = true;
+                }
+            }
+        }
+
+        words.get(start).cloned().unwrap_or_default()
+    }
+}
+
+/// Draws random words of bounded length from a rule of the grammar.
+///
+/// Every valid word is equally likely to be drawn, even for ambiguous grammars.
+/// Derivations are drawn uniformly, and a word with several parse trees is then only kept
+/// with the probability inverse to their number, so the more ambiguous the grammar,
+/// the more derivations have to be drawn on average.
+pub struct WordGenerator<'a> {
+    grammar: &'a Grammar,
+    start: usize,
+    max_length: usize,
+    // number of derivations of words of every length, for every rule
+    counts: Vec<Vec<u128>>,
+    alphabet: Vec<char>,
+}
+
+impl<'a> WordGenerator<'a> {
+    pub fn new(
+        grammar: &'a Grammar,
+        start: usize,
+        max_length: usize,
+    ) -> Result<Self, GeneratorError> {
+        let mut counts = vec![vec![0; max_length + 1]; grammar.rules.len()];
+
+        // every finite derivation is found after at most that many rounds,
+        // so anything that still changes afterwards has to be derived through a cycle
+        let max_rounds = (max_length + 1) * (grammar.rules.len() + 1) + 1;
+        let mut rounds = 0;
+        loop {
+            let mut changed = false;
+            for id in grammar.rule_ids() {
+                let derived = WordGenerator::rule_counts(grammar, &counts, id, max_length);
+                if derived != counts[id] {
+                    counts[id] = derived;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+            rounds += 1;
+            if rounds > max_rounds {
+                return Err(GeneratorError::InfiniteDerivations);
+            }
+        }
+
+        // the counts of the other rules only matter as far as they add up to the start one
+        if let Some(start_counts) = counts.get(start) {
+            start_counts
+                .iter()
+                .try_fold(0u128, |total, &count| total.checked_add(count))
+                .filter(|&total| total < u128::MAX)
+                .ok_or(GeneratorError::TooManyDerivations)?;
+        }
+
+        let alphabet: BTreeSet<_> = grammar
+            .rule_ids()
+            .filter_map(|id| match grammar.rule(id) {
+                Some(Rule::Terminal(terminal)) => Some(terminal.chars()),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+
+        Ok(WordGenerator {
+            grammar,
+            start,
+            max_length,
+            counts,
+            alphabet: alphabet.into_iter().collect(),
+        })
+    }
+
+    fn rule_counts(
+        grammar: &Grammar,
+        counts: &[Vec<u128>],
+        id: usize,
+        max_length: usize,
+    ) -> Vec<u128> {
+        let mut derived = vec![0u128; max_length + 1];
+        match grammar.rule(id) {
+            Some(Rule::Terminal(terminal)) => {
+                if let Some(count) = derived.get_mut(terminal.chars().count()) {
+                    *count = 1
+                }
+            }
+            Some(Rule::Nonterminal(rule)) => {
+                for subrule in rule.subrules.iter() {
+                    let sequence = WordGenerator::sequence_counts(counts, subrule, max_length);
+                    for (total, count) in derived.iter_mut().zip(sequence) {
+                        *total = total.saturating_add(count)
+                    }
+                }
+            }
+            None => (),
+        }
+        derived
+    }
+
+    fn sequence_counts(counts: &[Vec<u128>], rules: &[usize], max_length: usize) -> Vec<u128> {
+        let mut sequence = vec![0; max_length + 1];
+        sequence[0] = 1;
+        for &id in rules {
+            sequence = match counts.get(id) {
+                Some(rule_counts) => concatenate_counts(&sequence, rule_counts),
+                None => vec![0; max_length + 1],
+            }
+        }
+        sequence
+    }
+
+    /// Draws a word with every valid word being equally likely.
+    /// Returns `None` if there are no words short enough.
+    pub fn valid_word<R: Rng>(&self, rng: &mut R) -> Option<String> {
+        let lengths = self.counts.get(self.start)?;
+        if lengths.iter().all(|&count| count == 0) {
+            return None;
+        }
+
+        loop {
+            let length = choose_weighted(lengths, rng);
+            let mut word = String::new();
+            self.derive(self.start, length, rng, &mut word);
+
+            // a word with n derivations is drawn n times as often as an unambiguous one
+            let derivations = match self.grammar.count_derivations(&word, self.start) {
+                DerivationCount::Finite(derivations) => derivations,
+                DerivationCount::Infinite => unreachable!("the derivations have been counted"),
+            };
+            if rng.gen_range(0..derivations) == 0 {
+                return Some(word);
+            }
+        }
+    }
+
+    fn derive<R: Rng>(&self, id: usize, length: usize, rng: &mut R, word: &mut String) {
+        match self.grammar.rule(id).unwrap() {
+            Rule::Terminal(terminal) => word.push_str(terminal),
+            Rule::Nonterminal(rule) => {
+                let weights: Vec<_> = rule
+                    .subrules
+                    .iter()
+                    .map(|subrule| {
+                        WordGenerator::sequence_counts(&self.counts, subrule, self.max_length)
+                            [length]
+                    })
+                    .collect();
+                let subrule = &rule.subrules[choose_weighted(&weights, rng)];
+                self.derive_sequence(subrule, length, rng, word)
+            }
+        }
+    }
+
+    fn derive_sequence<R: Rng>(
+        &self,
+        rules: &[usize],
+        length: usize,
+        rng: &mut R,
+        word: &mut String,
+    ) {
+        let (&first, rest) = match rules.split_first() {
+            Some(split) => split,
+            None => return,
+        };
+
+        // split the length between the first rule and the remaining ones
+        let remaining = WordGenerator::sequence_counts(&self.counts, rest, self.max_length);
+        let weights: Vec<_> = (0..=length)
+            .map(|first_length| self.counts[first][first_length] * remaining[length - first_length])
+            .collect();
+        let first_length = choose_weighted(&weights, rng);
+
+        self.derive(first, first_length, rng, word);
+        self.derive_sequence(rest, length - first_length, rng, word)
+    }
+
+    /// Draws a word that is not accepted by the grammar, but is only a single character
+    /// substitution, insertion or deletion away from a valid word.
+    pub fn invalid_word<R: Rng>(&self, rng: &mut R) -> Option<String> {
+        let valid: Vec<_> = self.valid_word(rng)?.chars().collect();
+
+        let mut mutations = Vec::new();
+        for position in 0..=valid.len() {
+            if position < valid.len() {
+                let mut deleted = valid.clone();
+                deleted.remove(position);
+                mutations.push(deleted);
+            }
+            for &c in self.alphabet.iter() {
+                let mut inserted = valid.clone();
+                inserted.insert(position, c);
+                mutations.push(inserted);
+                if position < valid.len() && valid[position] != c {
+                    let mut substituted = valid.clone();
+                    substituted[position] = c;
+                    mutations.push(substituted);
+                }
+            }
+        }
+        mutations.shuffle(rng);
+
+        let mut tried = HashSet::new();
+        mutations
+            .into_iter()
+            .map(|mutation| mutation.into_iter().collect::<String>())
+            .filter(|mutation| tried.insert(mutation.clone()))
+            .find(|mutation| !self.grammar.recognize(mutation, self.start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashMap;
+
+    fn sample_grammar() -> Grammar {
+        Grammar::from("0: 4 1 5\n1: 2 3 | 3 2\n2: 4 4 | 5 5\n3: 4 5 | 5 4\n4: \"a\"\n5: \"b\"")
+    }
+
+    #[test]
+    fn enumerating_words() {
+        let grammar = sample_grammar();
+        assert!(grammar.enumerate_words(0, 5).is_empty());
+
+        let words = grammar.enumerate_words(0, 6);
+        assert_eq!(8, words.len());
+        assert!(words.contains("aaaabb"));
+        assert!(words.iter().all(|word| grammar.check_word(word)));
+
+        let recursive = Grammar::from("0: 1 | 1 0 2 |\n1: \"a\"\n2: \"bb\"");
+        let words: Vec<_> = recursive.enumerate_words(0, 7).into_iter().collect();
+        assert_eq!(vec!["", "a", "aaabbbb", "aabb", "aabbbb", "abb"], words);
+    }
+
+    #[test]
+    fn valid_words_are_accepted() {
+        // 42+ 42{n} 31{n}, as in the second part of the puzzle
+        let grammar = Grammar::from("0: 8 11\n8: 42 | 42 8\n11: 42 31 | 42 11 31\n42: 1 2 | 2 2\n31: 2 1\n1: \"a\"\n2: \"b\"");
+        let generator = WordGenerator::new(&grammar, 0, 20).unwrap();
+        let mut rng = StdRng::seed_from_u64(19);
+
+        for _ in 0..100 {
+            let word = generator.valid_word(&mut rng).unwrap();
+            assert!(word.len() <= 20);
+            assert!(grammar.check_word(&word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn invalid_words_are_close_but_rejected() {
+        let grammar = sample_grammar();
+        let generator = WordGenerator::new(&grammar, 0, 6).unwrap();
+        let mut rng = StdRng::seed_from_u64(19);
+
+        for _ in 0..100 {
+            let word = generator.invalid_word(&mut rng).unwrap();
+            assert!((5..=7).contains(&word.len()));
+            assert!(!grammar.check_word(&word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn unambiguous_words_are_uniform() {
+        let grammar = sample_grammar();
+        let generator = WordGenerator::new(&grammar, 0, 10).unwrap();
+        let mut rng = StdRng::seed_from_u64(19);
+
+        let mut frequencies = HashMap::new();
+        for _ in 0..8000 {
+            *frequencies
+                .entry(generator.valid_word(&mut rng).unwrap())
+                .or_insert(0) += 1;
+        }
+        assert_eq!(8, frequencies.len());
+        assert!(frequencies
+            .values()
+            .all(|&count| count > 800 && count < 1200));
+    }
+
+    #[test]
+    fn ambiguous_words_are_uniform() {
+        // "ab" has two derivations while "b" only has a single one
+        let grammar = Grammar::from("0: 1 | 2 | 3\n1: 4 5\n2: 4 5\n3: 5\n4: \"a\"\n5: \"b\"");
+        let generator = WordGenerator::new(&grammar, 0, 2).unwrap();
+        let mut rng = StdRng::seed_from_u64(19);
+
+        let ambiguous = (0..3000)
+            .filter(|_| generator.valid_word(&mut rng).unwrap() == "ab")
+            .count();
+        assert!(ambiguous > 1300 && ambiguous < 1700);
+
+        // a single word of every length, with the Catalan number of derivations
+        let catalan = Grammar::from("0: 0 0 | 1\n1: \"a\"");
+        let generator = WordGenerator::new(&catalan, 0, 4).unwrap();
+        let mut frequencies = HashMap::new();
+        for _ in 0..4000 {
+            *frequencies
+                .entry(generator.valid_word(&mut rng).unwrap())
+                .or_insert(0) += 1;
+        }
+        assert_eq!(4, frequencies.len());
+        assert!(frequencies
+            .values()
+            .all(|&count| count > 800 && count < 1200));
+    }
+
+    #[test]
+    fn derivation_counts_do_not_overflow() {
+        // 2^n words of every length n, each with a Catalan number of derivations
+        let grammar = Grammar::from("0: 0 0 | 1 | 2\n1: \"a\"\n2: \"b\"");
+        assert!(WordGenerator::new(&grammar, 0, 40).is_ok());
+        assert_eq!(
+            Some(GeneratorError::TooManyDerivations),
+            WordGenerator::new(&grammar, 0, 80).err()
+        );
+    }
+
+    #[test]
+    fn generation_edge_cases() {
+        let grammar = sample_grammar();
+        let mut rng = StdRng::seed_from_u64(19);
+        assert_eq!(
+            None,
+            WordGenerator::new(&grammar, 0, 5)
+                .unwrap()
+                .valid_word(&mut rng)
+        );
+
+        let empty = Grammar::default();
+        let generator = WordGenerator::new(&empty, 0, 3).unwrap();
+        assert_eq!(None, generator.valid_word(&mut rng));
+        assert_eq!(None, generator.invalid_word(&mut rng));
+
+        let cyclic = Grammar::from("0: 0 | 1\n1: \"a\"");
+        assert!(WordGenerator::new(&cyclic, 0, 3).is_err());
+        assert_eq!(1, cyclic.enumerate_words(0, 3).len());
+    }
+}
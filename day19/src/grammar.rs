@@ -0,0 +1,241 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::validation::DiagnosticKind;
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+
+/// Largest supported rule id, as the rules are stored densely by their ids.
+pub const MAX_RULE_ID: usize = u16::MAX as usize;
+
+#[derive(Debug, Clone, Default)]
+pub struct Grammar {
+    pub(crate) rules: Vec<Option<Rule>>,
+}
+
+// use `Grammar::load` for rules that are not known to be well-formed
+impl From<&str> for Grammar {
+    fn from(rules: &str) -> Self {
+        let unsorted_rules: Vec<_> = rules
+            .lines()
+            .map(|raw_rule| {
+                let id_rule: Vec<_> = raw_rule.splitn(2, ':').collect();
+                let rule_id: usize = id_rule[0].parse().expect("failed to parse rule id");
+                let rule = Rule::try_from(id_rule[1]).expect("failed to parse rule");
+                (rule_id, rule)
+            })
+            .collect();
+
+        let mut grammar = Grammar::default();
+        for (id, rule) in unsorted_rules.into_iter() {
+            grammar.set_rule(id, rule)
+        }
+
+        grammar
+    }
+}
+
+impl Grammar {
+    pub fn rule(&self, id: usize) -> Option<&Rule> {
+        self.rules.get(id).and_then(Option::as_ref)
+    }
+
+    /// Panics if the id is greater than `MAX_RULE_ID`.
+    pub fn set_rule(&mut self, id: usize, rule: Rule) {
+        assert!(id <= MAX_RULE_ID, "rule id {} is too large", id);
+        if id >= self.rules.len() {
+            self.rules.resize(id + 1, None)
+        }
+        self.rules[id] = Some(rule)
+    }
+
+    /// Identifiers of all the defined rules.
+    pub fn rule_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| rule.is_some())
+            .map(|(id, _)| id)
+    }
+
+    // rules directly referenced by the rule
+    pub(crate) fn dependencies(&self, id: usize) -> Vec<usize> {
+        match self.rule(id) {
+            Some(Rule::Nonterminal(rule)) => rule.subrules.iter().flatten().copied().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Rules that might be used when deriving a word from any of the given rules,
+    /// including those rules themselves.
+    pub fn reachable_rules<I: IntoIterator<Item = usize>>(&self, from: I) -> BTreeSet<usize> {
+        let mut reachable = BTreeSet::new();
+        let mut stack: Vec<_> = from.into_iter().collect();
+        while let Some(id) = stack.pop() {
+            if reachable.insert(id) {
+                stack.extend(self.dependencies(id))
+            }
+        }
+        reachable
+    }
+
+    /// Rules that can (directly or indirectly) refer back to themselves.
+    pub fn recursive_rules(&self) -> BTreeSet<usize> {
+        self.rule_ids()
+            .filter(|&id| self.reachable_rules(self.dependencies(id)).contains(&id))
+            .collect()
+    }
+
+    // rules that can derive a word consisting of terminals satisfying the predicate,
+    // indexed by rule id
+    fn rules_deriving<F: Fn(&str) -> bool>(&self, terminal_predicate: F) -> Vec<bool> {
+        let mut deriving = vec![false; self.rules.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for id in self.rule_ids() {
+                if deriving[id] {
+                    continue;
+                }
+                let derives = match self.rule(id).unwrap() {
+                    Rule::Terminal(terminal) => terminal_predicate(terminal),
+                    Rule::Nonterminal(rule) => rule.subrules.iter().any(|subrule| {
+                        subrule
+                            .iter()
+                            .all(|&id| deriving.get(id).copied().unwrap_or(false))
+                    }),
+                };
+                if derives {
+                    deriving[id] = true;
+                    changed = true;
+                }
+            }
+        }
+        deriving
+    }
+
+    // rules that can match an empty word
+    pub(crate) fn nullable_rules(&self) -> Vec<bool> {
+        self.rules_deriving(str::is_empty)
+    }
+
+    // rules that can match at least a single (finite) word
+    pub(crate) fn productive_rules(&self) -> Vec<bool> {
+        self.rules_deriving(|_| true)
+    }
+
+    pub fn check_word(&self, word: &str) -> bool {
+        self.recognize(word, 0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// Matches the exact string, which might be empty.
+    Terminal(String),
+    Nonterminal(NonterminalRule),
+}
+
+pub type Subrule = Vec<usize>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonterminalRule {
+    /// Alternatives of the rule, an empty subrule matches an empty word.
+    pub subrules: Vec<Subrule>,
+}
+
+impl TryFrom<&str> for Rule {
+    type Error = DiagnosticKind;
+
+    fn try_from(raw: &str) -> Result<Self, Self::Error> {
+        let raw = raw.trim();
+        if raw.contains('"') {
+            // the quotes must surround the entire rule and can't appear within it
+            let terminal = raw
+                .strip_prefix('"')
+                .and_then(|raw| raw.strip_suffix('"'))
+                .filter(|terminal| !terminal.contains('"'))
+                .ok_or_else(|| DiagnosticKind::MalformedTerminal(raw.to_string()))?;
+            return Ok(Rule::Terminal(terminal.to_string()));
+        }
+
+        let subrules = raw
+            .split('|')
+            .map(|subrule| {
+                subrule
+                    .split_ascii_whitespace()
+                    .map(|rule| {
+                        rule.parse()
+                            .map_err(|_| DiagnosticKind::InvalidReference(rule.to_string()))
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Rule::Nonterminal(NonterminalRule { subrules }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_rules() {
+        assert_eq!(
+            Ok(Rule::Terminal("ab".to_string())),
+            Rule::try_from(r#" "ab""#)
+        );
+        assert_eq!(Ok(Rule::Terminal(String::new())), Rule::try_from(r#" """#));
+        assert_eq!(
+            Ok(Rule::Nonterminal(NonterminalRule {
+                subrules: vec![vec![1, 2], vec![3], vec![]]
+            })),
+            Rule::try_from(" 1 2 | 3 |")
+        );
+        assert_eq!(
+            Err(DiagnosticKind::MalformedTerminal(r#""a" | 2"#.to_string())),
+            Rule::try_from(r#" "a" | 2"#)
+        );
+        assert_eq!(
+            Err(DiagnosticKind::InvalidReference("x".to_string())),
+            Rule::try_from(" 1 x")
+        );
+    }
+
+    #[test]
+    fn recursive_rules() {
+        let grammar = Grammar::from("0: 1 2\n1: 3 | 1 3\n2: 4\n3: \"a\"\n4: 5 3\n5: 2 |");
+        assert_eq!(
+            vec![1, 2, 4, 5],
+            grammar.recursive_rules().into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![1, 3],
+            grammar
+                .reachable_rules(vec![1])
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn nullable_rules() {
+        let grammar = Grammar::from("0: 1 2\n1: \"\"\n2: 1 | 3\n3: \"a\"\n4: 3 |");
+        assert_eq!(
+            vec![true, true, true, false, true],
+            grammar.nullable_rules()
+        );
+    }
+}
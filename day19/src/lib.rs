@@ -0,0 +1,213 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::Infallible;
+use std::path::Path;
+use utils::input_read::{self, InputError};
+use utils::solution::Solution;
+
+pub use compiler::{CompileError, Dfa};
+pub use earley::{DerivationCount, ParseTree};
+pub use generator::{GeneratorError, WordGenerator};
+pub use grammar::{Grammar, NonterminalRule, Rule, Subrule, MAX_RULE_ID};
+pub use validation::{validate_rules, Diagnostic, DiagnosticKind};
+
+mod compiler;
+mod earley;
+mod generator;
+mod grammar;
+mod validation;
+
+pub fn part1(input: &[String]) -> usize {
+    let grammar = Grammar::load(&input[0], 0).expect("invalid grammar rules");
+
+    // without any loops the words can be matched in linear time,
+    // unless the automaton would get too big
+    match grammar.to_dfa(0) {
+        Ok(dfa) => input[1]
+            .split('\n')
+            .filter(|word| dfa.matches(word))
+            .count(),
+        Err(_) => input[1]
+            .split('\n')
+            .filter(|word| grammar.check_word(word))
+            .count(),
+    }
+}
+
+fn do_part2_grammar_change(grammar: &mut Grammar) {
+    grammar.set_rule(
+        8,
+        Rule::Nonterminal(NonterminalRule {
+            subrules: vec![vec![42], vec![42, 8]],
+        }),
+    );
+    grammar.set_rule(
+        11,
+        Rule::Nonterminal(NonterminalRule {
+            subrules: vec![vec![42, 31], vec![42, 11, 31]],
+        }),
+    );
+}
+
+pub fn part2(input: &[String]) -> usize {
+    let mut grammar = Grammar::load(&input[0], 0).expect("invalid grammar rules");
+    do_part2_grammar_change(&mut grammar);
+
+    input[1]
+        .split('\n')
+        .filter(|word| grammar.check_word(word))
+        .count()
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Vec<String>;
+    type ParseError = Infallible;
+    type Part1Output = usize;
+    type Part2Output = usize;
+
+    fn parse_input<P: AsRef<Path>>(path: P) -> Result<Self::Input, InputError<Self::ParseError>> {
+        input_read::read_into_string_groups(path).map_err(InputError::Io)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Part1Output> {
+        Some(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2Output> {
+        Some(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_small_sample() {
+        let input = vec![
+            r#"0: 1 2
+1: "a"
+2: 1 3 | 3 1
+3: "b""#
+                .to_string(),
+            r#"aab
+aba"#
+                .to_string(),
+        ];
+
+        let expected = 2;
+
+        assert_eq!(expected, part1(&input));
+    }
+
+    #[test]
+    fn part1_sample_input() {
+        let input = vec![
+            r#"0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: "a"
+5: "b""#
+                .to_string(),
+            r#"ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb"#
+                .to_string(),
+        ];
+
+        let expected = 2;
+
+        assert_eq!(expected, part1(&input));
+    }
+
+    #[test]
+    fn part1_falls_back_on_too_large_grammars() {
+        // every rule uses the next one twice, with the last one matching one or no `a`
+        let mut rules: Vec<_> = (0..40)
+            .map(|id| format!("{}: {} {}", id, id + 1, id + 1))
+            .collect();
+        rules.push("40: 41 | 42\n41: \"a\"\n42: \"\"".to_string());
+        let input = vec![rules.join("\n"), "aaaa\na\nab".to_string()];
+
+        let expected = 2;
+
+        assert_eq!(expected, part1(&input));
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = vec![
+            r#"42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: "a"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: "b"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1"#
+                .to_string(),
+            r#"abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#
+                .to_string(),
+        ];
+
+        let expected_p1 = 3;
+        let expected_p2 = 12;
+
+        assert_eq!(expected_p1, part1(&input));
+        assert_eq!(expected_p2, part2(&input));
+    }
+}
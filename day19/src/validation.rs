@@ -0,0 +1,329 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::grammar::{Grammar, Rule, MAX_RULE_ID};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// The line is not of the `id: rule` form.
+    MissingSeparator,
+    InvalidRuleId(String),
+    /// The rule id is greater than `MAX_RULE_ID`.
+    RuleIdTooLarge(usize),
+    InvalidReference(String),
+    MalformedTerminal(String),
+    DuplicateRule {
+        id: usize,
+        previous_line: usize,
+    },
+    UndefinedReference {
+        rule: usize,
+        reference: usize,
+    },
+    UndefinedStartRule(usize),
+    /// The rule can never be used to match a word starting from the start rule.
+    UnreachableRule(usize),
+    /// The rule is a part of a cycle that never ends with terminals, so it can't match anything.
+    UnproductiveCycle(usize),
+}
+
+impl DiagnosticKind {
+    /// Whether the grammar can still be used despite the problem.
+    pub fn is_warning(&self) -> bool {
+        matches!(self, DiagnosticKind::UnreachableRule(_))
+    }
+}
+
+impl Display for DiagnosticKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagnosticKind::MissingSeparator => write!(f, "expected rule in the 'id: rule' form"),
+            DiagnosticKind::InvalidRuleId(id) => write!(f, "invalid rule id '{}'", id),
+            DiagnosticKind::RuleIdTooLarge(id) => {
+                write!(f, "rule id {} exceeds the maximum of {}", id, MAX_RULE_ID)
+            }
+            DiagnosticKind::InvalidReference(reference) => {
+                write!(f, "invalid rule reference '{}'", reference)
+            }
+            DiagnosticKind::MalformedTerminal(terminal) => {
+                write!(f, "malformed terminal {}", terminal)
+            }
+            DiagnosticKind::DuplicateRule { id, previous_line } => write!(
+                f,
+                "rule {} is already defined at line {}",
+                id, previous_line
+            ),
+            DiagnosticKind::UndefinedReference { rule, reference } => {
+                write!(f, "rule {} refers to undefined rule {}", rule, reference)
+            }
+            DiagnosticKind::UndefinedStartRule(id) => write!(f, "start rule {} is undefined", id),
+            DiagnosticKind::UnreachableRule(id) => {
+                write!(f, "rule {} is unreachable from the start rule", id)
+            }
+            DiagnosticKind::UnproductiveCycle(id) => {
+                write!(
+                    f,
+                    "rule {} is part of a cycle that can't match anything",
+                    id
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// 1-based line the problem was found at, if it concerns a particular line.
+    pub line: Option<usize>,
+    pub kind: DiagnosticKind,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.kind),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+// loads all well-formed rules, reporting everything that is wrong with them
+fn load_rules(source: &str, start: usize) -> (Grammar, Vec<Diagnostic>) {
+    let mut diagnostics = Vec::new();
+    let mut report = |line: usize, kind| {
+        diagnostics.push(Diagnostic {
+            line: Some(line),
+            kind,
+        })
+    };
+
+    let mut grammar = Grammar::default();
+    // lines at which the rules are defined, even the malformed ones
+    let mut definitions = HashMap::new();
+
+    for (index, raw) in source.lines().enumerate() {
+        let line = index + 1;
+        if raw.trim().is_empty() {
+            continue;
+        }
+
+        let id_rule: Vec<_> = raw.splitn(2, ':').collect();
+        if id_rule.len() != 2 {
+            report(line, DiagnosticKind::MissingSeparator);
+            continue;
+        }
+
+        let id: usize = match id_rule[0].trim().parse() {
+            Ok(id) => id,
+            Err(_) => {
+                report(
+                    line,
+                    DiagnosticKind::InvalidRuleId(id_rule[0].trim().to_string()),
+                );
+                continue;
+            }
+        };
+
+        if id > MAX_RULE_ID {
+            report(line, DiagnosticKind::RuleIdTooLarge(id));
+            continue;
+        }
+
+        if let Some(&previous_line) = definitions.get(&id) {
+            report(line, DiagnosticKind::DuplicateRule { id, previous_line });
+            continue;
+        }
+        definitions.insert(id, line);
+
+        match Rule::try_from(id_rule[1]) {
+            Ok(rule) => grammar.set_rule(id, rule),
+            Err(kind) => report(line, kind),
+        }
+    }
+
+    for id in grammar.rule_ids() {
+        let mut undefined: Vec<_> = grammar
+            .dependencies(id)
+            .into_iter()
+            .filter(|reference| !definitions.contains_key(reference))
+            .collect();
+        undefined.sort_unstable();
+        undefined.dedup();
+        for reference in undefined {
+            report(
+                definitions[&id],
+                DiagnosticKind::UndefinedReference {
+                    rule: id,
+                    reference,
+                },
+            )
+        }
+    }
+
+    let reachable = grammar.reachable_rules(vec![start]);
+    let productive = grammar.productive_rules();
+    let recursive = grammar.recursive_rules();
+    for id in grammar.rule_ids() {
+        if !reachable.contains(&id) {
+            report(definitions[&id], DiagnosticKind::UnreachableRule(id))
+        }
+        if recursive.contains(&id) && !productive[id] {
+            report(definitions[&id], DiagnosticKind::UnproductiveCycle(id))
+        }
+    }
+
+    if !definitions.contains_key(&start) {
+        diagnostics.push(Diagnostic {
+            line: None,
+            kind: DiagnosticKind::UndefinedStartRule(start),
+        })
+    }
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+    (grammar, diagnostics)
+}
+
+/// Finds all the problems with the rules, without using them for anything.
+pub fn validate_rules(source: &str, start: usize) -> Vec<Diagnostic> {
+    load_rules(source, start).1
+}
+
+impl Grammar {
+    /// Loads the rules, failing with every problem found in them unless all of them
+    /// are just warnings.
+    pub fn load(source: &str, start: usize) -> Result<Self, Vec<Diagnostic>> {
+        let (grammar, diagnostics) = load_rules(source, start);
+        if diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.kind.is_warning())
+        {
+            Ok(grammar)
+        } else {
+            Err(diagnostics)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(line: usize, kind: DiagnosticKind) -> Diagnostic {
+        Diagnostic {
+            line: Some(line),
+            kind,
+        }
+    }
+
+    #[test]
+    fn reporting_every_problem() {
+        let source = r#"0: 1 2 | 9
+1: "a"
+2: 1 | 3
+3: 2 3
+x: 1
+4 "b"
+1: "b"
+5: "c
+6: 1 two
+
+7: 0"#;
+
+        assert_eq!(
+            vec![
+                at(
+                    1,
+                    DiagnosticKind::UndefinedReference {
+                        rule: 0,
+                        reference: 9
+                    }
+                ),
+                at(4, DiagnosticKind::UnproductiveCycle(3)),
+                at(5, DiagnosticKind::InvalidRuleId("x".to_string())),
+                at(6, DiagnosticKind::MissingSeparator),
+                at(
+                    7,
+                    DiagnosticKind::DuplicateRule {
+                        id: 1,
+                        previous_line: 2
+                    }
+                ),
+                at(8, DiagnosticKind::MalformedTerminal("\"c".to_string())),
+                at(9, DiagnosticKind::InvalidReference("two".to_string())),
+                at(11, DiagnosticKind::UnreachableRule(7)),
+            ],
+            validate_rules(source, 0)
+        );
+        assert!(Grammar::load(source, 0).is_err());
+    }
+
+    #[test]
+    fn warnings_do_not_prevent_loading() {
+        let source = "0: 1\n1: \"a\"\n2: 1 1";
+        assert_eq!(
+            vec![at(3, DiagnosticKind::UnreachableRule(2))],
+            validate_rules(source, 0)
+        );
+        let grammar = Grammar::load(source, 0).unwrap();
+        assert!(grammar.check_word("a"));
+
+        let diagnostics = Grammar::load("0: 1\n1: \"a\"", 3).unwrap_err();
+        assert_eq!(
+            Diagnostic {
+                line: None,
+                kind: DiagnosticKind::UndefinedStartRule(3)
+            },
+            diagnostics[0]
+        );
+    }
+
+    #[test]
+    fn huge_rule_ids_are_rejected() {
+        let source = format!("0: 1 | {0}\n1: \"a\"\n{0}: 1 1", usize::MAX);
+        assert_eq!(
+            vec![
+                at(
+                    1,
+                    DiagnosticKind::UndefinedReference {
+                        rule: 0,
+                        reference: usize::MAX
+                    }
+                ),
+                at(3, DiagnosticKind::RuleIdTooLarge(usize::MAX)),
+            ],
+            validate_rules(&source, 0)
+        );
+
+        let source = format!("0: {}\n{}: \"a\"", MAX_RULE_ID, MAX_RULE_ID);
+        assert!(Grammar::load(&source, 0).unwrap().check_word("a"));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            "line 3: rule 2 refers to undefined rule 7",
+            at(
+                3,
+                DiagnosticKind::UndefinedReference {
+                    rule: 2,
+                    reference: 7
+                }
+            )
+            .to_string()
+        );
+    }
+}
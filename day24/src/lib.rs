@@ -0,0 +1,179 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use day17::automaton::{HexAxial, LifeRule, SparseAutomaton};
+use day17::Point;
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::path::Path;
+use utils::input_read::{self, InputError};
+use utils::solution::Solution;
+
+const EAST: char = 'e';
+const SOUTH: char = 's';
+const WEST: char = 'w';
+const NORTH: char = 'n';
+
+const DAYS_TO_SIMULATE: usize = 100;
+
+// represent Hexagon by its axial coordinates
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+struct Hexagon {
+    location: Point<2>,
+}
+
+impl From<&String> for Hexagon {
+    fn from(raw: &String) -> Self {
+        let mut reference = Point::origin();
+
+        let mut iter = raw.chars();
+        while let Some(current) = iter.next() {
+            match current {
+                EAST => reference += HexAxial::EAST,
+                SOUTH => match iter.next().expect("invalid hex direction") {
+                    EAST => reference += HexAxial::SOUTH_EAST,
+                    WEST => reference += HexAxial::SOUTH_WEST,
+                    _ => panic!("invalid hex direction"),
+                },
+                WEST => reference += HexAxial::WEST,
+                NORTH => match iter.next().expect("invalid hex direction") {
+                    WEST => reference += HexAxial::NORTH_WEST,
+                    EAST => reference += HexAxial::NORTH_EAST,
+                    _ => panic!("invalid hex direction"),
+                },
+                _ => panic!("invalid hex direction"),
+            }
+        }
+
+        Hexagon {
+            location: reference,
+        }
+    }
+}
+
+fn initially_flipped(input: &[String]) -> HashSet<Hexagon> {
+    let mut active = HashSet::new();
+    input.iter().map(Hexagon::from).for_each(|hex| {
+        if active.contains(&hex) {
+            active.remove(&hex);
+        } else {
+            active.insert(hex);
+        }
+    });
+
+    active
+}
+
+pub fn part1(input: &[String]) -> usize {
+    initially_flipped(input).len()
+}
+
+pub fn part2(input: &[String]) -> usize {
+    let initial = initially_flipped(input).into_iter().map(|hex| hex.location);
+    // black tiles with zero or more than 2 black neighbours are flipped to white,
+    // white tiles with exactly 2 black neighbours are flipped to black
+    let mut tiles = SparseAutomaton::new(HexAxial, LifeRule::new(&[2], &[1, 2]), initial);
+
+    for _ in 0..DAYS_TO_SIMULATE {
+        tiles.step_par();
+    }
+
+    tiles.alive_count()
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Vec<String>;
+    type ParseError = Infallible;
+    type Part1Output = usize;
+    type Part2Output = usize;
+
+    fn parse_input<P: AsRef<Path>>(path: P) -> Result<Self::Input, InputError<Self::ParseError>> {
+        input_read::read_line_input(path)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Part1Output> {
+        Some(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2Output> {
+        Some(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_sample_input() {
+        let input = vec![
+            "sesenwnenenewseeswwswswwnenewsewsw".to_string(),
+            "neeenesenwnwwswnenewnwwsewnenwseswesw".to_string(),
+            "seswneswswsenwwnwse".to_string(),
+            "nwnwneseeswswnenewneswwnewseswneseene".to_string(),
+            "swweswneswnenwsewnwneneseenw".to_string(),
+            "eesenwseswswnenwswnwnwsewwnwsene".to_string(),
+            "sewnenenenesenwsewnenwwwse".to_string(),
+            "wenwwweseeeweswwwnwwe".to_string(),
+            "wsweesenenewnwwnwsenewsenwwsesesenwne".to_string(),
+            "neeswseenwwswnwswswnw".to_string(),
+            "nenwswwsewswnenenewsenwsenwnesesenew".to_string(),
+            "enewnwewneswsewnwswenweswnenwsenwsw".to_string(),
+            "sweneswneswneneenwnewenewwneswswnese".to_string(),
+            "swwesenesewenwneswnwwneseswwne".to_string(),
+            "enesenwswwswneneswsenwnewswseenwsese".to_string(),
+            "wnwnesenesenenwwnenwsewesewsesesew".to_string(),
+            "nenewswnwewswnenesenwnesewesw".to_string(),
+            "eneswnwswnwsenenwnwnwwseeswneewsenese".to_string(),
+            "neswnwewnwnwseenwseesewsenwsweewe".to_string(),
+            "wseweeenwnesenwwwswnew".to_string(),
+        ];
+
+        let expected = 10;
+
+        assert_eq!(expected, part1(&input))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = vec![
+            "sesenwnenenewseeswwswswwnenewsewsw".to_string(),
+            "neeenesenwnwwswnenewnwwsewnenwseswesw".to_string(),
+            "seswneswswsenwwnwse".to_string(),
+            "nwnwneseeswswnenewneswwnewseswneseene".to_string(),
+            "swweswneswnenwsewnwneneseenw".to_string(),
+            "eesenwseswswnenwswnwnwsewwnwsene".to_string(),
+            "sewnenenenesenwsewnenwwwse".to_string(),
+            "wenwwweseeeweswwwnwwe".to_string(),
+            "wsweesenenewnwwnwsenewsenwwsesesenwne".to_string(),
+            "neeswseenwwswnwswswnw".to_string(),
+            "nenwswwsewswnenenewsenwsenwnesesenew".to_string(),
+            "enewnwewneswsewnwswenweswnenwsenwsw".to_string(),
+            "sweneswneswneneenwnewenewwneswswnese".to_string(),
+            "swwesenesewenwneswnwwneseswwne".to_string(),
+            "enesenwswwswneneswsenwnewswseenwsese".to_string(),
+            "wnwnesenesenenwwnenwsewesewsesesew".to_string(),
+            "nenewswnwewswnenesenwnesewesw".to_string(),
+            "eneswnwswnwsenenwnwnwwseeswneewsenese".to_string(),
+            "neswnwewnwnwseenwseesewsenwsweewe".to_string(),
+            "wseweeenwnesenwwwswnew".to_string(),
+        ];
+
+        let expected = 2208;
+
+        assert_eq!(expected, part2(&input))
+    }
+}
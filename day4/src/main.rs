@@ -12,105 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::passport::{Passport, RawPassport};
-use std::convert::TryFrom;
-use utils::input_read;
-
-mod passport;
-
-fn part1(input: &[String]) -> usize {
-    input
-        .iter()
-        .map(RawPassport::try_from)
-        .filter(Result::is_ok)
-        .count()
-}
-
-fn part2(input: &[String]) -> usize {
-    input
-        .iter()
-        .map(RawPassport::try_from)
-        .filter(Result::is_ok)
-        .map(|raw_pass| Passport::try_from(raw_pass.unwrap()))
-        .filter(Result::is_ok)
-        .filter(|pass| pass.as_ref().unwrap().validate())
-        .count()
-}
+use day4::Day4;
+use utils::input_read::InputError;
+use utils::solution::Solution;
 
 #[cfg(not(tarpaulin))]
-fn main() {
-    let input = input_read::read_into_string_groups("input").expect("failed to read input file");
-    let part1_result = part1(&input);
+fn main() -> Result<(), InputError> {
+    let input = Day4::parse_input("input")?;
+
+    let part1_result = Day4::part1(&input).expect("failed to solve part1");
     println!("Part 1 result is {}", part1_result);
 
-    let part2_result = part2(&input);
+    let part2_result = Day4::part2(&input).expect("failed to solve part2");
     println!("Part 2 result is {}", part2_result);
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn part1_sample_input() {
-        let input = vec![
-            "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
-byr:1937 iyr:2017 cid:147 hgt:183cm"
-                .to_string(),
-            "iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
-hcl:#cfa07d byr:1929"
-                .to_string(),
-            "hcl:#ae17e1 iyr:2013
-eyr:2024
-ecl:brn pid:760753108 byr:1931
-hgt:179cm"
-                .to_string(),
-            "hcl:#cfa07d eyr:2025 pid:166559648
-iyr:2011 ecl:brn hgt:59in
-"
-            .to_string(),
-        ];
-
-        let expected = 2;
-
-        assert_eq!(expected, part1(&input))
-    }
-
-    #[test]
-    fn part2_sample_input() {
-        let input = vec![
-            "eyr:1972 cid:100
-hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926"
-                .to_string(),
-            "iyr:2019
-hcl:#602927 eyr:1967 hgt:170cm
-ecl:grn pid:012533040 byr:1946"
-                .to_string(),
-            "hcl:dab227 iyr:2012
-ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277"
-                .to_string(),
-            "hgt:59cm ecl:zzz
-eyr:2038 hcl:74454a iyr:2023
-pid:3556412378 byr:2007"
-                .to_string(),
-            "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
-hcl:#623a2f"
-                .to_string(),
-            "eyr:2029 ecl:blu cid:129 byr:1989
-iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm"
-                .to_string(),
-            "hcl:#888785
-hgt:164cm byr:2001 iyr:2015 cid:88
-pid:545766238 ecl:hzl
-eyr:2022"
-                .to_string(),
-            "iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719
-"
-            .to_string(),
-        ];
-
-        let expected = 4;
 
-        assert_eq!(expected, part2(&input))
-    }
+    Ok(())
 }
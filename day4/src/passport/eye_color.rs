@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use serde::de::{self, Deserialize, Deserializer};
+use serde::{Serialize, Serializer};
 use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
 
 const COLOR_AMBER_ABBREVIATION: &str = "amb";
 const COLOR_BLUE_ABBREVIATION: &str = "blu";
@@ -22,7 +25,8 @@ const COLOR_GREEN_ABBREVIATION: &str = "grn";
 const COLOR_HAZEL_ABBREVIATION: &str = "hzl";
 const COLOR_OTHER_ABBREVIATION: &str = "oth";
 
-pub(super) enum EyeColor {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EyeColor {
     Amber,
     Blue,
     Brown,
@@ -32,8 +36,8 @@ pub(super) enum EyeColor {
     Other,
 }
 
-#[derive(Debug)]
-pub(super) struct InvalidEyeColor;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidEyeColor;
 
 impl<'a> TryFrom<&'a str> for EyeColor {
     type Error = InvalidEyeColor;
@@ -51,3 +55,60 @@ impl<'a> TryFrom<&'a str> for EyeColor {
         }
     }
 }
+
+impl EyeColor {
+    pub fn abbreviation(self) -> &'static str {
+        match self {
+            EyeColor::Amber => COLOR_AMBER_ABBREVIATION,
+            EyeColor::Blue => COLOR_BLUE_ABBREVIATION,
+            EyeColor::Brown => COLOR_BROWN_ABBREVIATION,
+            EyeColor::Gray => COLOR_GRAY_ABBREVIATION,
+            EyeColor::Green => COLOR_GREEN_ABBREVIATION,
+            EyeColor::Hazel => COLOR_HAZEL_ABBREVIATION,
+            EyeColor::Other => COLOR_OTHER_ABBREVIATION,
+        }
+    }
+}
+
+impl Display for EyeColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.abbreviation())
+    }
+}
+
+impl Serialize for EyeColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.abbreviation())
+    }
+}
+
+impl<'de> Deserialize<'de> for EyeColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        EyeColor::try_from(&*raw)
+            .map_err(|_| de::Error::custom(format!("invalid eye colour {}", raw)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn abbreviations_round_trip() {
+        let colors = [
+            EyeColor::Amber,
+            EyeColor::Blue,
+            EyeColor::Brown,
+            EyeColor::Gray,
+            EyeColor::Green,
+            EyeColor::Hazel,
+            EyeColor::Other,
+        ];
+
+        for color in colors.iter() {
+            assert_eq!(Ok(*color), EyeColor::try_from(&*color.to_string()));
+        }
+        assert!(EyeColor::try_from("zzz").is_err());
+    }
+}
@@ -12,36 +12,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub(super) struct HairColor(String);
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HairColor(String);
 
 impl<S: Into<String>> From<S> for HairColor {
     fn from(str: S) -> Self {
-        HairColor(str.into())
+        // hex digits are case insensitive, so always keep them lowercase
+        HairColor(str.into().to_ascii_lowercase())
     }
 }
 
 impl HairColor {
-    pub(super) fn validate_in_passport(&self) -> bool {
-        if !self.0.is_ascii() || self.0.len() != 7 {
-            return false;
-        }
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
 
-        for (i, char) in self.0.chars().enumerate() {
-            if i == 0 {
-                if char != '#' {
-                    return false;
-                } else {
-                    continue;
-                }
-            } else {
-                match char {
-                    '0'..='9' | 'a'..='f' => continue,
-                    _ => return false,
-                }
-            }
-        }
+impl Display for HairColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
 
-        true
+impl<'de> Deserialize<'de> for HairColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        super::batch_value(deserializer, "hair colour").map(HairColor::from)
     }
 }
 
@@ -50,16 +48,11 @@ mod tests {
     use super::*;
 
     #[test]
-    fn hair_color_validation() {
-        let valid = vec!["#123abc", "#123abf"];
-        let invalid = vec!["", "#123abz", "123abcd"];
-
-        for valid in valid {
-            assert!(HairColor(valid.to_string()).validate_in_passport())
-        }
-
-        for invalid in invalid {
-            assert!(!HairColor(invalid.to_string()).validate_in_passport())
-        }
+    fn hair_colors_are_lowercase() {
+        assert_eq!("#123abc", HairColor::from("#123ABC").to_string());
+        assert_eq!(
+            HairColor::from("#123abc"),
+            serde_json::from_str(r##""#123aBc""##).unwrap()
+        );
     }
 }
@@ -12,15 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use serde::de::{self, Deserialize, Deserializer};
+use serde::{Serialize, Serializer};
 use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
 
 const HEIGHT_METRIC_UNIT: &str = "cm";
 const HEIGHT_IMPERIAL_UNIT: &str = "in";
 
-#[derive(Debug)]
-pub(super) struct InvalidHeight;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidHeight;
 
-pub(super) enum Height {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Height {
     Metric(usize),
     Imperial(usize),
 }
@@ -35,7 +39,7 @@ impl<'a> TryFrom<&'a str> for Height {
         }
 
         let mut chars: Vec<_> = value.chars().collect();
-        let unit = vec![chars.pop().unwrap(), chars.pop().unwrap()]
+        let unit = [chars.pop().unwrap(), chars.pop().unwrap()]
             .iter()
             .rev()
             .collect::<String>();
@@ -54,30 +58,53 @@ impl<'a> TryFrom<&'a str> for Height {
 }
 
 impl Height {
-    fn value(&self) -> usize {
+    pub fn value(&self) -> usize {
         match self {
             Height::Metric(value) => *value,
             Height::Imperial(value) => *value,
         }
     }
 
-    fn is_metric(&self) -> bool {
+    pub fn is_metric(&self) -> bool {
         matches!(self, Height::Metric(_))
     }
 
-    fn is_imperial(&self) -> bool {
+    pub fn is_imperial(&self) -> bool {
         matches!(self, Height::Imperial(_))
     }
 
-    pub(super) fn validate_in_passport(&self) -> bool {
-        let value = self.value();
-        if self.is_metric() && !(150..=193).contains(&value) {
-            return false;
+    /// Converts the height into centimetres, rounded to the nearest one.
+    /// Returns `None` if the height is too large to be converted.
+    pub fn to_metric(self) -> Option<Height> {
+        match self {
+            Height::Metric(_) => Some(self),
+            Height::Imperial(inches) => inches
+                .checked_mul(254)
+                .and_then(|tenths| tenths.checked_add(50))
+                .map(|tenths| Height::Metric(tenths / 100)),
         }
-        if self.is_imperial() && !(59..=76).contains(&value) {
-            return false;
+    }
+}
+
+impl Display for Height {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Height::Metric(value) => write!(f, "{}{}", value, HEIGHT_METRIC_UNIT),
+            Height::Imperial(value) => write!(f, "{}{}", value, HEIGHT_IMPERIAL_UNIT),
         }
-        true
+    }
+}
+
+impl Serialize for Height {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Height {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Height::try_from(&*raw).map_err(|_| de::Error::custom(format!("invalid height {}", raw)))
     }
 }
 
@@ -102,27 +129,19 @@ mod tests {
     }
 
     #[test]
-    fn passport_validation() {
-        let good_metric = [150, 170, 193];
-        let bad_metric = [149, 194, 0, 2000];
-
-        let good_imperial = [59, 65, 67];
-        let bad_imperial = [58, 77, 0, 321];
-
-        for good in good_metric.iter() {
-            assert!(Height::Metric(*good).validate_in_passport())
-        }
-
-        for bad in bad_metric.iter() {
-            assert!(!Height::Metric(*bad).validate_in_passport())
-        }
-
-        for good in good_imperial.iter() {
-            assert!(Height::Imperial(*good).validate_in_passport())
-        }
-
-        for bad in bad_imperial.iter() {
-            assert!(!Height::Imperial(*bad).validate_in_passport())
+    fn height_normalisation() {
+        assert_eq!(Some(Height::Metric(183)), Height::Metric(183).to_metric());
+        assert_eq!(Some(Height::Metric(150)), Height::Imperial(59).to_metric());
+        assert_eq!(Some(Height::Metric(193)), Height::Imperial(76).to_metric());
+        assert_eq!(Some(Height::Metric(152)), Height::Imperial(60).to_metric());
+        assert_eq!(None, Height::Imperial(usize::MAX / 200).to_metric());
+        assert_eq!(
+            Some(Height::Metric(usize::MAX)),
+            Height::Metric(usize::MAX).to_metric()
+        );
+
+        for raw in ["183cm", "60in"].iter() {
+            assert_eq!(*raw, Height::try_from(*raw).unwrap().to_string());
         }
     }
 }
@@ -12,17 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use self::eye_color::{EyeColor, InvalidEyeColor};
-use self::hair_color::HairColor;
-use self::height::{Height, InvalidHeight};
-use self::passport_id::PassportId;
-use std::collections::HashMap;
+pub use self::eye_color::{EyeColor, InvalidEyeColor};
+pub use self::hair_color::HairColor;
+pub use self::height::{Height, InvalidHeight};
+pub use self::passport_id::PassportId;
+pub use self::serialization::{to_batch, to_csv, to_json};
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
 
 mod eye_color;
 mod hair_color;
 mod height;
 mod passport_id;
+mod serialization;
 
 const BIRTH_YEAR_ABBREVIATION: &str = "byr";
 const ISSUE_YEAR_ABBREVIATION: &str = "iyr";
@@ -33,188 +37,266 @@ const EYE_COLOR_ABBREVIATION: &str = "ecl";
 const PASSPORT_ID_ABBREVIATION: &str = "pid";
 const COUNTRY_ID_ABBREVIATION: &str = "cid";
 
-#[derive(Debug)]
-pub(crate) enum MalformedPassport {
-    MissingFields,
-    MalformedData(String),
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MalformedPassport {
+    /// Field was not of form `key:value`.
+    MalformedField(String),
+    DuplicateField(String),
+    MissingField(&'static str),
+    InvalidYear {
+        field: &'static str,
+        value: String,
+    },
+    InvalidHeight(InvalidHeight),
+    InvalidEyeColor(InvalidEyeColor),
 }
 
 impl From<InvalidHeight> for MalformedPassport {
     fn from(err: InvalidHeight) -> Self {
-        MalformedPassport::MalformedData(format!("invalid height - {:?}", err))
+        MalformedPassport::InvalidHeight(err)
     }
 }
 
 impl From<InvalidEyeColor> for MalformedPassport {
     fn from(err: InvalidEyeColor) -> Self {
-        MalformedPassport::MalformedData(format!("invalid height - {:?}", err))
+        MalformedPassport::InvalidEyeColor(err)
     }
 }
 
-pub(crate) struct Passport {
+impl Display for MalformedPassport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MalformedPassport::MalformedField(field) => write!(f, "invalid field - {}", field),
+            MalformedPassport::DuplicateField(name) => write!(f, "duplicate field - {}", name),
+            MalformedPassport::MissingField(name) => write!(f, "missing field - {}", name),
+            MalformedPassport::InvalidYear { field, value } => {
+                write!(f, "invalid year in {} - {}", field, value)
+            }
+            MalformedPassport::InvalidHeight(_) => write!(f, "invalid height"),
+            MalformedPassport::InvalidEyeColor(_) => write!(f, "invalid eye colour"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Passport {
     birth_year: usize,
     issue_year: usize,
     expiration_year: usize,
     height: Height,
     hair_color: HairColor,
-    #[allow(dead_code)] // we don't use this field for anything
     eye_color: EyeColor,
     passport_id: PassportId,
-    #[allow(dead_code)] // we don't use this field for anything
-    country_id: Option<usize>,
+    // kept verbatim, as nothing restricts what the ids look like
+    #[serde(deserialize_with = "optional_batch_value")]
+    country_id: Option<String>,
+}
+
+// the values have to fit into a single `key:value` field of a batch record,
+// so that deserialized passports can always be written back as a batch
+fn batch_value<'de, D: Deserializer<'de>>(deserializer: D, name: &str) -> Result<String, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    if value.contains(|c: char| c.is_whitespace() || c == ':') {
+        return Err(de::Error::custom(format!("invalid {} {:?}", name, value)));
+    }
+    Ok(value)
 }
 
-impl TryFrom<RawPassport> for Passport {
+fn optional_batch_value<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    match Option::<String>::deserialize(deserializer)? {
+        Some(value) => {
+            batch_value(de::value::StringDeserializer::new(value), "country id").map(Some)
+        }
+        None => Ok(None),
+    }
+}
+
+fn required_field<'a>(
+    raw: &'a RawPassport,
+    name: &'static str,
+) -> Result<&'a str, MalformedPassport> {
+    raw.get(name).ok_or(MalformedPassport::MissingField(name))
+}
+
+fn required_year(raw: &RawPassport, name: &'static str) -> Result<usize, MalformedPassport> {
+    let value = required_field(raw, name)?;
+    value.parse().map_err(|_| MalformedPassport::InvalidYear {
+        field: name,
+        value: value.to_owned(),
+    })
+}
+
+impl<'a> TryFrom<&'a RawPassport> for Passport {
     type Error = MalformedPassport;
 
-    fn try_from(value: RawPassport) -> Result<Self, Self::Error> {
+    fn try_from(value: &'a RawPassport) -> Result<Self, Self::Error> {
         Ok(Passport {
-            birth_year: value.birth_year,
-            issue_year: value.issue_year,
-            expiration_year: value.expiration_year,
-            height: Height::try_from(&*value.height)?,
-            hair_color: HairColor::from(value.hair_color),
-            eye_color: EyeColor::try_from(&*value.eye_color)?,
-            passport_id: PassportId::from(value.passport_id),
-            country_id: value.country_id,
+            birth_year: required_year(value, BIRTH_YEAR_ABBREVIATION)?,
+            issue_year: required_year(value, ISSUE_YEAR_ABBREVIATION)?,
+            expiration_year: required_year(value, EXPIRATION_YEAR_ABBREVIATION)?,
+            height: Height::try_from(required_field(value, HEIGHT_ABBREVIATION)?)?,
+            hair_color: HairColor::from(required_field(value, HAIR_COLOR_ABBREVIATION)?),
+            eye_color: EyeColor::try_from(required_field(value, EYE_COLOR_ABBREVIATION)?)?,
+            passport_id: PassportId::from(required_field(value, PASSPORT_ID_ABBREVIATION)?),
+            country_id: value.get(COUNTRY_ID_ABBREVIATION).map(str::to_owned),
         })
     }
 }
 
 impl Passport {
-    pub(crate) fn validate(&self) -> bool {
-        if self.birth_year < 1920 || self.birth_year > 2002 {
-            return false;
-        }
+    pub fn birth_year(&self) -> usize {
+        self.birth_year
+    }
 
-        if self.issue_year < 2010 || self.issue_year > 2020 {
-            return false;
-        }
+    pub fn issue_year(&self) -> usize {
+        self.issue_year
+    }
 
-        if self.expiration_year < 2020 || self.expiration_year > 2030 {
-            return false;
-        }
+    pub fn expiration_year(&self) -> usize {
+        self.expiration_year
+    }
 
-        if !self.height.validate_in_passport() {
-            return false;
-        }
+    pub fn height(&self) -> Height {
+        self.height
+    }
 
-        if !self.hair_color.validate_in_passport() {
-            return false;
-        }
+    pub fn hair_color(&self) -> &HairColor {
+        &self.hair_color
+    }
 
-        if !self.passport_id.validate_in_passport() {
-            return false;
-        }
+    pub fn eye_color(&self) -> EyeColor {
+        self.eye_color
+    }
+
+    pub fn passport_id(&self) -> &PassportId {
+        &self.passport_id
+    }
 
-        true
+    pub fn country_id(&self) -> Option<&str> {
+        self.country_id.as_deref()
     }
 }
 
-pub(crate) struct RawPassport {
-    birth_year: usize,
-    issue_year: usize,
-    expiration_year: usize,
-    height: String,
-    hair_color: String,
-    eye_color: String,
-    passport_id: String,
-    country_id: Option<usize>,
+/// Fields of a single record of the batch file, in the order they were listed in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawPassport {
+    fields: Vec<(String, String)>,
 }
 
-impl<'a> TryFrom<&'a String> for RawPassport {
-    type Error = MalformedPassport;
+impl RawPassport {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
 
-    fn try_from(value: &'a String) -> Result<Self, Self::Error> {
-        let fields = value.split_ascii_whitespace();
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for RawPassport {
+    type Error = MalformedPassport;
 
-        let mut mandatory_passport_fields = HashMap::new();
-        let mut country_id = None;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let mut fields: Vec<(String, String)> = Vec::new();
 
-        for field in fields {
+        for field in value.split_ascii_whitespace() {
             let name_value = field.split(':').collect::<Vec<_>>();
             if name_value.len() != 2 {
-                return Err(MalformedPassport::MalformedData(format!(
-                    "invalid field: {}",
-                    field
-                )));
+                return Err(MalformedPassport::MalformedField(field.to_owned()));
             }
 
             let name = name_value[0];
-            let value = name_value[1];
-
-            match name {
-                BIRTH_YEAR_ABBREVIATION => {
-                    mandatory_passport_fields.insert(BIRTH_YEAR_ABBREVIATION, value);
-                }
-                ISSUE_YEAR_ABBREVIATION => {
-                    mandatory_passport_fields.insert(ISSUE_YEAR_ABBREVIATION, value);
-                }
-                EXPIRATION_YEAR_ABBREVIATION => {
-                    mandatory_passport_fields.insert(EXPIRATION_YEAR_ABBREVIATION, value);
-                }
-                HEIGHT_ABBREVIATION => {
-                    mandatory_passport_fields.insert(HEIGHT_ABBREVIATION, value);
-                }
-                HAIR_COLOR_ABBREVIATION => {
-                    mandatory_passport_fields.insert(HAIR_COLOR_ABBREVIATION, value);
-                }
-                EYE_COLOR_ABBREVIATION => {
-                    mandatory_passport_fields.insert(EYE_COLOR_ABBREVIATION, value);
-                }
-                PASSPORT_ID_ABBREVIATION => {
-                    mandatory_passport_fields.insert(PASSPORT_ID_ABBREVIATION, value);
-                }
-                COUNTRY_ID_ABBREVIATION => country_id = Some(value),
-                _ => {
-                    return Err(MalformedPassport::MalformedData(
-                        "unknown field".to_string(),
-                    ));
-                }
+            if fields.iter().any(|(existing, _)| existing == name) {
+                return Err(MalformedPassport::DuplicateField(name.to_owned()));
             }
+
+            fields.push((name.to_owned(), name_value[1].to_owned()));
         }
 
-        if mandatory_passport_fields.len() != 7 {
-            return Err(MalformedPassport::MissingFields);
+        Ok(RawPassport { fields })
+    }
+}
+
+impl<'a> TryFrom<&'a String> for RawPassport {
+    type Error = MalformedPassport;
+
+    fn try_from(value: &'a String) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_passport_parsing() {
+        let raw = RawPassport::try_from("hcl:#ae17e1 iyr:2013\neyr:2024 foo:bar").unwrap();
+        assert_eq!(Some("2013"), raw.get("iyr"));
+        assert_eq!(None, raw.get("byr"));
+        assert_eq!(
+            vec![
+                ("hcl", "#ae17e1"),
+                ("iyr", "2013"),
+                ("eyr", "2024"),
+                ("foo", "bar")
+            ],
+            raw.fields().collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            Err(MalformedPassport::MalformedField("iyr2013".to_string())),
+            RawPassport::try_from("hcl:#ae17e1 iyr2013")
+        );
+        assert_eq!(
+            Err(MalformedPassport::DuplicateField("iyr".to_string())),
+            RawPassport::try_from("iyr:2013 iyr:2014")
+        );
+    }
+
+    #[test]
+    fn passport_conversion_errors() {
+        let convert = |record: &str| Passport::try_from(&RawPassport::try_from(record).unwrap());
+
+        let valid = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017 hgt:183cm";
+        let passport = convert(valid).unwrap();
+        assert_eq!(Height::Metric(183), passport.height());
+        assert_eq!(EyeColor::Gray, passport.eye_color());
+        assert_eq!(None, passport.country_id());
+        for id in &["007", "abc", "129"] {
+            let passport = convert(&format!("{} cid:{}", valid, id)).unwrap();
+            assert_eq!(Some(*id), passport.country_id());
         }
 
-        Ok(RawPassport {
-            birth_year: mandatory_passport_fields
-                .get(BIRTH_YEAR_ABBREVIATION)
-                .unwrap()
-                .parse()
-                .map_err(|_| MalformedPassport::MissingFields)?,
-            issue_year: mandatory_passport_fields
-                .get(ISSUE_YEAR_ABBREVIATION)
-                .unwrap()
-                .parse()
-                .map_err(|_| MalformedPassport::MissingFields)?,
-            expiration_year: mandatory_passport_fields
-                .get(EXPIRATION_YEAR_ABBREVIATION)
-                .unwrap()
-                .parse()
-                .map_err(|_| MalformedPassport::MissingFields)?,
-            height: mandatory_passport_fields
-                .get(HEIGHT_ABBREVIATION)
-                .unwrap()
-                .parse()
-                .map_err(|_| MalformedPassport::MissingFields)?,
-            hair_color: mandatory_passport_fields
-                .get(HAIR_COLOR_ABBREVIATION)
-                .unwrap()
-                .parse()
-                .map_err(|_| MalformedPassport::MissingFields)?,
-            eye_color: mandatory_passport_fields
-                .get(EYE_COLOR_ABBREVIATION)
-                .unwrap()
-                .parse()
-                .map_err(|_| MalformedPassport::MissingFields)?,
-            passport_id: mandatory_passport_fields
-                .get(PASSPORT_ID_ABBREVIATION)
-                .unwrap()
-                .parse()
-                .map_err(|_| MalformedPassport::MissingFields)?,
-            country_id: country_id.map(|id| id.parse().ok()).flatten(),
-        })
+        assert_eq!(
+            Err(MalformedPassport::MissingField(HEIGHT_ABBREVIATION)),
+            convert("ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017")
+        );
+        assert_eq!(
+            Err(MalformedPassport::InvalidEyeColor(InvalidEyeColor)),
+            convert(&valid.replace("ecl:gry", "ecl:zzz"))
+        );
+        assert_eq!(
+            "invalid eye colour",
+            MalformedPassport::from(InvalidEyeColor).to_string()
+        );
+        assert_eq!(
+            Err(MalformedPassport::InvalidHeight(InvalidHeight)),
+            convert(&valid.replace("hgt:183cm", "hgt:183"))
+        );
+        assert_eq!(
+            Err(MalformedPassport::InvalidYear {
+                field: BIRTH_YEAR_ABBREVIATION,
+                value: "soon".to_string()
+            }),
+            convert(&valid.replace("byr:1937", "byr:soon"))
+        );
     }
 }
@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub(super) struct PassportId(String);
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PassportId(String);
 
 impl<S: Into<String>> From<S> for PassportId {
     fn from(str: S) -> Self {
@@ -21,35 +25,19 @@ impl<S: Into<String>> From<S> for PassportId {
 }
 
 impl PassportId {
-    pub(super) fn validate_in_passport(&self) -> bool {
-        // it must be numeric of size 9
-        if !self.0.is_ascii() || self.0.len() != 9 {
-            return false;
-        }
-        for char in self.0.chars() {
-            if !char.is_numeric() {
-                return false;
-            }
-        }
-        true
+    pub fn as_str(&self) -> &str {
+        &self.0
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn passport_id_validation() {
-        let valid = vec!["000000001", "600304001"];
-        let invalid = vec!["", "0123456789", "00000001", "aaaaaaaaa"];
-
-        for valid in valid {
-            assert!(PassportId(valid.to_string()).validate_in_passport())
-        }
+impl Display for PassportId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
 
-        for invalid in invalid {
-            assert!(!PassportId(invalid.to_string()).validate_in_passport())
-        }
+impl<'de> Deserialize<'de> for PassportId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        super::batch_value(deserializer, "passport id").map(PassportId)
     }
 }
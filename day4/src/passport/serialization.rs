@@ -0,0 +1,228 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    Passport, BIRTH_YEAR_ABBREVIATION, COUNTRY_ID_ABBREVIATION, EXPIRATION_YEAR_ABBREVIATION,
+    EYE_COLOR_ABBREVIATION, HAIR_COLOR_ABBREVIATION, HEIGHT_ABBREVIATION, ISSUE_YEAR_ABBREVIATION,
+    PASSPORT_ID_ABBREVIATION,
+};
+use std::fmt::{self, Display, Formatter};
+
+const CSV_HEADER: &str =
+    "birth_year,issue_year,expiration_year,height,hair_color,eye_color,passport_id,country_id";
+
+// quotes the value if it would otherwise break the row apart
+fn csv_field(value: String) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+impl Passport {
+    /// Copy of the passport with its height expressed in centimetres.
+    /// Returns `None` if the height is too large to be converted.
+    pub fn normalised(&self) -> Option<Passport> {
+        Some(Passport {
+            height: self.height.to_metric()?,
+            ..self.clone()
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("passport is always serializable")
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Passport> {
+        serde_json::from_str(json)
+    }
+
+    fn to_csv_row(&self) -> String {
+        [
+            self.birth_year.to_string(),
+            self.issue_year.to_string(),
+            self.expiration_year.to_string(),
+            self.height.to_string(),
+            self.hair_color.to_string(),
+            self.eye_color.to_string(),
+            self.passport_id.to_string(),
+            self.country_id.clone().unwrap_or_default(),
+        ]
+        .iter()
+        .cloned()
+        .map(csv_field)
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
+
+/// Canonical batch format: every field on a single line in a fixed order.
+impl Display for Passport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{} {}:{} {}:{} {}:{} {}:{} {}:{} {}:{}",
+            BIRTH_YEAR_ABBREVIATION,
+            self.birth_year,
+            ISSUE_YEAR_ABBREVIATION,
+            self.issue_year,
+            EXPIRATION_YEAR_ABBREVIATION,
+            self.expiration_year,
+            HEIGHT_ABBREVIATION,
+            self.height,
+            HAIR_COLOR_ABBREVIATION,
+            self.hair_color,
+            EYE_COLOR_ABBREVIATION,
+            self.eye_color,
+            PASSPORT_ID_ABBREVIATION,
+            self.passport_id
+        )?;
+        if let Some(country_id) = &self.country_id {
+            write!(f, " {}:{}", COUNTRY_ID_ABBREVIATION, country_id)?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders the passports as a batch file, with the records separated by blank lines.
+pub fn to_batch(passports: &[Passport]) -> String {
+    passports
+        .iter()
+        .map(|passport| format!("{}\n", passport))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders the passports as a JSON array.
+pub fn to_json(passports: &[Passport]) -> String {
+    serde_json::to_string_pretty(passports).expect("passports are always serializable")
+}
+
+/// Renders the passports as CSV with a header row. Missing country ids are left empty.
+pub fn to_csv(passports: &[Passport]) -> String {
+    let mut csv = format!("{}\n", CSV_HEADER);
+    for passport in passports {
+        csv.push_str(&passport.to_csv_row());
+        csv.push('\n');
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::passport::{Height, RawPassport};
+    use std::convert::TryFrom;
+
+    fn parse(record: &str) -> Passport {
+        Passport::try_from(&RawPassport::try_from(record).unwrap()).unwrap()
+    }
+
+    fn sample_passports() -> Vec<Passport> {
+        vec![
+            parse("pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980\nhcl:#623A2F"),
+            parse(
+                "eyr:2029 ecl:blu cid:129 byr:1989\niyr:2014 pid:896056539 hcl:#a97842 hgt:165cm",
+            ),
+            parse("byr:1990 iyr:2015 eyr:2025 hgt:170cm hcl:#000000 ecl:oth pid:000000001 cid:007"),
+            parse("byr:1991 iyr:2016 eyr:2026 hgt:60in hcl:#ffffff ecl:amb pid:000000002 cid:n/a"),
+        ]
+    }
+
+    #[test]
+    fn batch_round_trip() {
+        let passports = sample_passports();
+        let batch = to_batch(&passports);
+
+        assert_eq!(
+            "byr:1980 iyr:2012 eyr:2030 hgt:74in hcl:#623a2f ecl:grn pid:087499704\n\n\
+             byr:1989 iyr:2014 eyr:2029 hgt:165cm hcl:#a97842 ecl:blu pid:896056539 cid:129\n\n\
+             byr:1990 iyr:2015 eyr:2025 hgt:170cm hcl:#000000 ecl:oth pid:000000001 cid:007\n\n\
+             byr:1991 iyr:2016 eyr:2026 hgt:60in hcl:#ffffff ecl:amb pid:000000002 cid:n/a\n",
+            batch
+        );
+
+        let parsed: Vec<_> = batch.split("\n\n").map(parse).collect();
+        assert_eq!(passports, parsed);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let passports = sample_passports();
+
+        assert_eq!(
+            r##"{"birth_year":1980,"issue_year":2012,"expiration_year":2030,"height":"74in","hair_color":"#623a2f","eye_color":"grn","passport_id":"087499704","country_id":null}"##,
+            passports[0].to_json()
+        );
+        assert_eq!(
+            passports[1],
+            Passport::from_json(&passports[1].to_json()).unwrap()
+        );
+
+        let parsed: Vec<Passport> = serde_json::from_str(&to_json(&passports)).unwrap();
+        assert_eq!(passports, parsed);
+
+        let invalid = passports[0].to_json().replace("74in", "74ft");
+        assert!(Passport::from_json(&invalid).is_err());
+    }
+
+    #[test]
+    fn json_values_have_to_fit_into_batch_fields() {
+        let json = sample_passports()[1].to_json();
+        for (valid, invalid) in [
+            ("896056539", "896 056539"),
+            ("#a97842", "#a97842\\n"),
+            ("#a97842", "#a9:842"),
+            ("\"129\"", "\"1\\t29\""),
+        ]
+        .iter()
+        {
+            let invalid = json.replace(valid, invalid);
+            assert!(Passport::from_json(&invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn csv_export() {
+        let mut passports = sample_passports();
+        passports[1].hair_color = "#a9,\"42".into();
+
+        let expected = "\
+birth_year,issue_year,expiration_year,height,hair_color,eye_color,passport_id,country_id
+1980,2012,2030,74in,#623a2f,grn,087499704,
+1989,2014,2029,165cm,\"#a9,\"\"42\",blu,896056539,129
+1990,2015,2025,170cm,#000000,oth,000000001,007
+1991,2016,2026,60in,#ffffff,amb,000000002,n/a
+";
+        assert_eq!(expected, to_csv(&passports));
+    }
+
+    #[test]
+    fn heights_are_normalised_to_centimetres() {
+        let passports = sample_passports();
+
+        let normalised = passports[0].normalised().unwrap();
+        assert_eq!(Height::Metric(188), normalised.height());
+        assert_eq!(Some(passports[1].clone()), passports[1].normalised());
+        assert_eq!(
+            "byr:1980 iyr:2012 eyr:2030 hgt:188cm hcl:#623a2f ecl:grn pid:087499704",
+            normalised.to_string()
+        );
+
+        let mut huge = passports[0].clone();
+        huge.height = Height::Imperial(usize::MAX);
+        assert_eq!(None, huge.normalised());
+    }
+}
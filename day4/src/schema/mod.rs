@@ -0,0 +1,408 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use self::rules::{Any, Digits, HexColor, Measurement, OneOf, YearRange};
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::fs;
+use std::path::Path;
+use utils::input_read::InputError;
+
+mod rules;
+
+const DEFAULT_SCHEMA: &str = include_str!("default_schema.txt");
+
+/// Constraint on the value of a single field, returning the reason for rejecting it.
+pub trait FieldRule: Debug + Send + Sync {
+    fn check(&self, value: &str) -> Result<(), String>;
+}
+
+/// Builds a rule out of the arguments given to its kind in the schema definition.
+pub type RuleConstructor = fn(&[&str]) -> Result<Box<dyn FieldRule>, String>;
+
+/// Kinds of rules that can be referred to by name in schema definitions.
+#[derive(Clone)]
+pub struct RuleKinds {
+    constructors: HashMap<String, RuleConstructor>,
+}
+
+impl Default for RuleKinds {
+    fn default() -> Self {
+        let mut kinds = RuleKinds {
+            constructors: HashMap::new(),
+        };
+        kinds.register("year", YearRange::build);
+        kinds.register("measurement", Measurement::build);
+        kinds.register("hex-color", HexColor::build);
+        kinds.register("one-of", OneOf::build);
+        kinds.register("digits", Digits::build);
+        kinds.register("any", Any::build);
+        kinds
+    }
+}
+
+impl RuleKinds {
+    /// Makes the kind available to schemas, replacing any existing kind of the same name.
+    pub fn register<S: Into<String>>(&mut self, name: S, constructor: RuleConstructor) {
+        self.constructors.insert(name.into(), constructor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaError {
+    /// Line didn't have the field name, requirement and kind.
+    MissingColumns,
+    InvalidRequirement(String),
+    UnknownKind(String),
+    InvalidArguments {
+        kind: String,
+        reason: String,
+    },
+    DuplicateField(String),
+}
+
+#[derive(Debug)]
+struct FieldSpec {
+    name: String,
+    required: bool,
+    rule: Box<dyn FieldRule>,
+}
+
+/// Set of fields a record is allowed to have alongside the rules for their values.
+#[derive(Debug)]
+pub struct Schema {
+    fields: Vec<FieldSpec>,
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Schema::parse(DEFAULT_SCHEMA).expect("default schema is malformed")
+    }
+}
+
+impl Schema {
+    /// Parses the definition with one field per line, as `<field> <required|optional> <kind> [arguments...]`.
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn parse(definition: &str) -> Result<Self, InputError<SchemaError>> {
+        Self::parse_with(definition, &RuleKinds::default())
+    }
+
+    pub fn parse_with(
+        definition: &str,
+        kinds: &RuleKinds,
+    ) -> Result<Self, InputError<SchemaError>> {
+        let mut fields: Vec<FieldSpec> = Vec::new();
+
+        for (i, line) in definition.lines().enumerate() {
+            let text = line.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+
+            let field = Self::parse_field(text, kinds).and_then(|field| {
+                if fields.iter().any(|existing| existing.name == field.name) {
+                    Err(SchemaError::DuplicateField(field.name))
+                } else {
+                    Ok(field)
+                }
+            });
+
+            match field {
+                Ok(field) => fields.push(field),
+                Err(err) => {
+                    return Err(InputError::Parse {
+                        line: i + 1,
+                        text: line.to_owned(),
+                        err,
+                    })
+                }
+            }
+        }
+
+        Ok(Schema { fields })
+    }
+
+    fn parse_field(text: &str, kinds: &RuleKinds) -> Result<FieldSpec, SchemaError> {
+        let columns: Vec<_> = text.split_ascii_whitespace().collect();
+        if columns.len() < 3 {
+            return Err(SchemaError::MissingColumns);
+        }
+
+        let required = match columns[1] {
+            "required" => true,
+            "optional" => false,
+            other => return Err(SchemaError::InvalidRequirement(other.to_owned())),
+        };
+
+        let kind = columns[2];
+        let constructor = kinds
+            .constructors
+            .get(kind)
+            .ok_or_else(|| SchemaError::UnknownKind(kind.to_owned()))?;
+        let rule = constructor(&columns[3..]).map_err(|reason| SchemaError::InvalidArguments {
+            kind: kind.to_owned(),
+            reason,
+        })?;
+
+        Ok(FieldSpec {
+            name: columns[0].to_owned(),
+            required,
+            rule,
+        })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, InputError<SchemaError>> {
+        Self::load_with(path, &RuleKinds::default())
+    }
+
+    pub fn load_with<P: AsRef<Path>>(
+        path: P,
+        kinds: &RuleKinds,
+    ) -> Result<Self, InputError<SchemaError>> {
+        Self::parse_with(&fs::read_to_string(path)?, kinds)
+    }
+
+    /// Checks all fields of the record, reporting every one of them that is invalid,
+    /// missing while required or not part of the schema.
+    pub fn validate<'a, I>(&self, record: I) -> ValidationReport
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let record: Vec<_> = record.into_iter().collect();
+        let mut failures = Vec::new();
+
+        for field in &self.fields {
+            match record.iter().find(|(name, _)| *name == field.name) {
+                Some((_, value)) => {
+                    if let Err(reason) = field.rule.check(value) {
+                        failures.push(FieldFailure::new(&field.name, FieldError::Invalid(reason)))
+                    }
+                }
+                None if field.required => {
+                    failures.push(FieldFailure::new(&field.name, FieldError::Missing))
+                }
+                None => (),
+            }
+        }
+
+        for (name, _) in record {
+            if !self.fields.iter().any(|field| field.name == name) {
+                failures.push(FieldFailure::new(name, FieldError::Unexpected))
+            }
+        }
+
+        ValidationReport { failures }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldError {
+    Missing,
+    /// Field is not defined in the schema.
+    Unexpected,
+    Invalid(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldFailure {
+    pub field: String,
+    pub error: FieldError,
+}
+
+impl FieldFailure {
+    fn new(field: &str, error: FieldError) -> Self {
+        FieldFailure {
+            field: field.to_owned(),
+            error,
+        }
+    }
+}
+
+impl Display for FieldFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.error {
+            FieldError::Missing => write!(f, "{}: field is missing", self.field),
+            FieldError::Unexpected => write!(f, "{}: field is not part of the schema", self.field),
+            FieldError::Invalid(reason) => write!(f, "{}: {}", self.field, reason),
+        }
+    }
+}
+
+/// Outcome of validating a record, listing every field that failed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub failures: Vec<FieldFailure>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Checks whether the record has exactly the expected fields, regardless of their values.
+    pub fn has_expected_fields(&self) -> bool {
+        self.failures
+            .iter()
+            .all(|failure| matches!(failure.error, FieldError::Invalid(_)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Palindrome;
+
+    impl FieldRule for Palindrome {
+        fn check(&self, value: &str) -> Result<(), String> {
+            if value.chars().rev().ne(value.chars()) {
+                return Err(format!("'{}' is not a palindrome", value));
+            }
+            Ok(())
+        }
+    }
+
+    fn palindrome(arguments: &[&str]) -> Result<Box<dyn FieldRule>, String> {
+        if !arguments.is_empty() {
+            return Err("palindromes take no arguments".to_string());
+        }
+        Ok(Box::new(Palindrome))
+    }
+
+    fn parse_error(definition: &str) -> (usize, SchemaError) {
+        match Schema::parse(definition) {
+            Err(InputError::Parse { line, err, .. }) => (line, err),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn every_failing_field_is_reported() {
+        let schema = Schema::default();
+        let record = vec![
+            ("byr", "2003"),
+            ("iyr", "2015"),
+            ("hgt", "190in"),
+            ("hcl", "#623a2f"),
+            ("ecl", "wat"),
+            ("pid", "000000001"),
+            ("foo", "bar"),
+        ];
+
+        let report = schema.validate(record);
+        let failures: Vec<_> = report.failures.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            vec![
+                "byr: 2003 is not between 1920 and 2002",
+                "eyr: field is missing",
+                "hgt: 190in is not between 59in and 76in",
+                "ecl: 'wat' is not one of amb, blu, brn, gry, grn, hzl, oth",
+                "foo: field is not part of the schema",
+            ],
+            failures
+        );
+        assert!(!report.is_valid());
+        assert!(!report.has_expected_fields());
+    }
+
+    #[test]
+    fn optional_fields_can_be_omitted() {
+        let schema = Schema::default();
+        let mut record = vec![
+            ("byr", "1980"),
+            ("iyr", "2012"),
+            ("eyr", "2030"),
+            ("hgt", "74in"),
+            ("hcl", "#623a2f"),
+            ("ecl", "grn"),
+            ("pid", "087499704"),
+        ];
+
+        assert!(schema.validate(record.clone()).is_valid());
+        record.push(("cid", "whatever"));
+        assert!(schema.validate(record.clone()).is_valid());
+
+        record[0] = ("byr", "1900");
+        let report = schema.validate(record);
+        assert!(!report.is_valid());
+        assert!(report.has_expected_fields());
+    }
+
+    #[test]
+    fn custom_kinds_can_be_registered() {
+        let definition = "
+            # custom rule next to the built-in one
+            name required palindrome
+            age optional year 0 150
+        ";
+        assert_eq!(
+            (3, SchemaError::UnknownKind("palindrome".to_string())),
+            parse_error(definition)
+        );
+
+        let mut kinds = RuleKinds::default();
+        kinds.register("palindrome", palindrome);
+        let schema = Schema::parse_with(definition, &kinds).unwrap();
+
+        assert!(schema.validate(vec![("name", "abba")]).is_valid());
+        assert_eq!(
+            vec![FieldFailure::new(
+                "name",
+                FieldError::Invalid("'abc' is not a palindrome".to_string())
+            )],
+            schema
+                .validate(vec![("name", "abc"), ("age", "42")])
+                .failures
+        );
+    }
+
+    #[test]
+    fn malformed_definitions_are_rejected() {
+        assert_eq!(
+            (1, SchemaError::MissingColumns),
+            parse_error("byr required")
+        );
+        assert_eq!(
+            (2, SchemaError::InvalidRequirement("maybe".to_string())),
+            parse_error("byr required any\niyr maybe any")
+        );
+        assert_eq!(
+            (
+                1,
+                SchemaError::InvalidArguments {
+                    kind: "digits".to_string(),
+                    reason: "'nine' is not a valid number".to_string()
+                }
+            ),
+            parse_error("pid required digits nine")
+        );
+        assert_eq!(
+            (3, SchemaError::DuplicateField("byr".to_string())),
+            parse_error("byr required any\n\nbyr optional any")
+        );
+    }
+
+    #[test]
+    fn schema_can_be_loaded_from_file() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/src/schema/default_schema.txt");
+        let schema = Schema::load(path).unwrap();
+        assert_eq!(Schema::default().fields.len(), schema.fields.len());
+
+        assert!(matches!(
+            Schema::load("this/schema/does/not/exist"),
+            Err(InputError::Io(_))
+        ));
+    }
+}
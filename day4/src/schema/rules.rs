@@ -0,0 +1,315 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::FieldRule;
+
+pub(super) fn parse_number(argument: &str) -> Result<usize, String> {
+    argument
+        .parse()
+        .map_err(|_| format!("'{}' is not a valid number", argument))
+}
+
+fn expect_arguments(arguments: &[&str], expected: usize) -> Result<(), String> {
+    if arguments.len() != expected {
+        return Err(format!(
+            "expected {} arguments, got {}",
+            expected,
+            arguments.len()
+        ));
+    }
+    Ok(())
+}
+
+fn parse_range(min: &str, max: &str) -> Result<(usize, usize), String> {
+    let (min, max) = (parse_number(min)?, parse_number(max)?);
+    if min > max {
+        return Err(format!("the range {}-{} is empty", min, max));
+    }
+    Ok((min, max))
+}
+
+/// Number between the two bounds, inclusive.
+#[derive(Debug)]
+pub(super) struct YearRange {
+    min: usize,
+    max: usize,
+}
+
+impl YearRange {
+    pub(super) fn build(arguments: &[&str]) -> Result<Box<dyn FieldRule>, String> {
+        expect_arguments(arguments, 2)?;
+        let (min, max) = parse_range(arguments[0], arguments[1])?;
+        Ok(Box::new(YearRange { min, max }))
+    }
+}
+
+impl FieldRule for YearRange {
+    fn check(&self, value: &str) -> Result<(), String> {
+        let year = parse_number(value)?;
+        if !(self.min..=self.max).contains(&year) {
+            return Err(format!(
+                "{} is not between {} and {}",
+                year, self.min, self.max
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Number followed by one of the units, each with its own inclusive range,
+/// for example `cm:150-193`.
+#[derive(Debug)]
+pub(super) struct Measurement {
+    units: Vec<(String, usize, usize)>,
+}
+
+impl Measurement {
+    pub(super) fn build(arguments: &[&str]) -> Result<Box<dyn FieldRule>, String> {
+        if arguments.is_empty() {
+            return Err("expected at least one unit".to_string());
+        }
+
+        let units = arguments
+            .iter()
+            .map(|argument| {
+                let invalid = || format!("'{}' is not of form <unit>:<min>-<max>", argument);
+                let (unit, range) = argument.split_once(':').ok_or_else(invalid)?;
+                let (min, max) = range.split_once('-').ok_or_else(invalid)?;
+                let (min, max) = parse_range(min, max)?;
+                Ok((unit.to_owned(), min, max))
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Box::new(Measurement { units }))
+    }
+}
+
+impl FieldRule for Measurement {
+    fn check(&self, value: &str) -> Result<(), String> {
+        // a unit can be the suffix of another one, as `m` is of `cm`,
+        // so only the units preceded by a number are considered
+        let mut not_a_number = None;
+        for (unit, min, max) in &self.units {
+            if let Some(amount) = value.strip_suffix(unit.as_str()) {
+                let amount = match parse_number(amount) {
+                    Ok(amount) => amount,
+                    Err(err) => {
+                        not_a_number.get_or_insert(err);
+                        continue;
+                    }
+                };
+                if !(*min..=*max).contains(&amount) {
+                    return Err(format!(
+                        "{}{} is not between {}{} and {}{}",
+                        amount, unit, min, unit, max, unit
+                    ));
+                }
+                return Ok(());
+            }
+        }
+        Err(not_a_number.unwrap_or_else(|| format!("'{}' does not have a known unit", value)))
+    }
+}
+
+/// `#` followed by exactly six lowercase hexadecimal digits.
+#[derive(Debug)]
+pub(super) struct HexColor;
+
+impl HexColor {
+    pub(super) fn build(arguments: &[&str]) -> Result<Box<dyn FieldRule>, String> {
+        expect_arguments(arguments, 0)?;
+        Ok(Box::new(HexColor))
+    }
+}
+
+impl FieldRule for HexColor {
+    fn check(&self, value: &str) -> Result<(), String> {
+        let valid = match value.strip_prefix('#') {
+            Some(digits) => {
+                digits.len() == 6 && digits.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'))
+            }
+            None => false,
+        };
+
+        if !valid {
+            return Err(format!("'{}' is not a colour of form #rrggbb", value));
+        }
+        Ok(())
+    }
+}
+
+/// Exactly one of the listed values.
+#[derive(Debug)]
+pub(super) struct OneOf(Vec<String>);
+
+impl OneOf {
+    pub(super) fn build(arguments: &[&str]) -> Result<Box<dyn FieldRule>, String> {
+        if arguments.is_empty() {
+            return Err("expected at least one allowed value".to_string());
+        }
+        Ok(Box::new(OneOf(
+            arguments.iter().map(|&value| value.to_owned()).collect(),
+        )))
+    }
+}
+
+impl FieldRule for OneOf {
+    fn check(&self, value: &str) -> Result<(), String> {
+        if !self.0.iter().any(|allowed| allowed == value) {
+            return Err(format!("'{}' is not one of {}", value, self.0.join(", ")));
+        }
+        Ok(())
+    }
+}
+
+/// Exactly the given number of decimal digits, leading zeroes included.
+#[derive(Debug)]
+pub(super) struct Digits(usize);
+
+impl Digits {
+    pub(super) fn build(arguments: &[&str]) -> Result<Box<dyn FieldRule>, String> {
+        expect_arguments(arguments, 1)?;
+        Ok(Box::new(Digits(parse_number(arguments[0])?)))
+    }
+}
+
+impl FieldRule for Digits {
+    fn check(&self, value: &str) -> Result<(), String> {
+        if value.len() != self.0 || !value.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("'{}' is not a {} digit number", value, self.0));
+        }
+        Ok(())
+    }
+}
+
+/// Accepts any value.
+#[derive(Debug)]
+pub(super) struct Any;
+
+impl Any {
+    pub(super) fn build(arguments: &[&str]) -> Result<Box<dyn FieldRule>, String> {
+        expect_arguments(arguments, 0)?;
+        Ok(Box::new(Any))
+    }
+}
+
+impl FieldRule for Any {
+    fn check(&self, _: &str) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn year_range_validation() {
+        let rule = YearRange::build(&["1920", "2002"]).unwrap();
+
+        for valid in ["1920", "1980", "2002"].iter() {
+            assert!(rule.check(valid).is_ok())
+        }
+        for invalid in ["1919", "2003", "", "year"].iter() {
+            assert!(rule.check(invalid).is_err())
+        }
+
+        assert!(YearRange::build(&["1920"]).is_err());
+        assert!(YearRange::build(&["1920", "later"]).is_err());
+        assert_eq!(
+            Err("the range 2002-1920 is empty".to_string()),
+            YearRange::build(&["2002", "1920"]).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn measurement_validation() {
+        let rule = Measurement::build(&["cm:150-193", "in:59-76"]).unwrap();
+
+        let good = ["150cm", "170cm", "193cm", "59in", "65in", "76in"];
+        let bad = [
+            "149cm", "194cm", "0cm", "2000cm", "58in", "77in", "0in", "321in", "60", "60c",
+            "60inch", "a60cm",
+        ];
+
+        for good in good.iter() {
+            assert!(rule.check(good).is_ok(), "{}", good)
+        }
+        for bad in bad.iter() {
+            assert!(rule.check(bad).is_err(), "{}", bad)
+        }
+
+        assert_eq!(
+            Err("194cm is not between 150cm and 193cm".to_string()),
+            rule.check("194cm")
+        );
+        assert!(Measurement::build(&[]).is_err());
+        assert!(Measurement::build(&["cm150-193"]).is_err());
+        assert!(Measurement::build(&["cm:150"]).is_err());
+        assert!(Measurement::build(&["cm:193-150"]).is_err());
+    }
+
+    #[test]
+    fn measurement_units_ending_in_other_units() {
+        let rule = Measurement::build(&["m:1-3", "cm:150-193"]).unwrap();
+
+        for good in ["2m", "170cm"].iter() {
+            assert!(rule.check(good).is_ok(), "{}", good)
+        }
+        assert_eq!(
+            Err("'a' is not a valid number".to_string()),
+            rule.check("am")
+        );
+        assert_eq!(
+            Err("200cm is not between 150cm and 193cm".to_string()),
+            rule.check("200cm")
+        );
+    }
+
+    #[test]
+    fn hex_color_validation() {
+        let rule = HexColor::build(&[]).unwrap();
+
+        for valid in ["#123abc", "#123abf"].iter() {
+            assert!(rule.check(valid).is_ok())
+        }
+        for invalid in ["", "#123abz", "123abcd", "#123ABC", "#123abcd"].iter() {
+            assert!(rule.check(invalid).is_err())
+        }
+    }
+
+    #[test]
+    fn one_of_validation() {
+        let rule = OneOf::build(&["amb", "blu"]).unwrap();
+
+        assert!(rule.check("blu").is_ok());
+        assert_eq!(
+            Err("'wat' is not one of amb, blu".to_string()),
+            rule.check("wat")
+        );
+        assert!(OneOf::build(&[]).is_err());
+    }
+
+    #[test]
+    fn digits_validation() {
+        let rule = Digits::build(&["9"]).unwrap();
+
+        for valid in ["000000001", "600304001"].iter() {
+            assert!(rule.check(valid).is_ok())
+        }
+        for invalid in ["", "0123456789", "00000001", "aaaaaaaaa", "٠١٢٣٤٥٦٧٨"].iter() {
+            assert!(rule.check(invalid).is_err())
+        }
+    }
+}
@@ -12,116 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
-use utils::input_read;
-
-struct Group {
-    size: usize,
-    answers: HashMap<char, usize>,
-}
-
-impl<'a> From<&'a String> for Group {
-    fn from(answers_raw: &'a String) -> Self {
-        let mut answers = HashMap::new();
-
-        let group_answers: Vec<_> = answers_raw.split_ascii_whitespace().collect();
-        let size = group_answers.len();
-
-        group_answers
-            .into_iter()
-            .flat_map(|answer| answer.chars())
-            .for_each(|char| *answers.entry(char).or_insert(0) += 1);
-
-        Group { size, answers }
-    }
-}
-
-impl Group {
-    fn len(&self) -> usize {
-        self.answers.len()
-    }
-
-    fn all_yes(&self) -> usize {
-        self.answers
-            .iter()
-            .filter(|(_, &count)| count == self.size)
-            .count()
-    }
-}
-
-fn part1(input: &[String]) -> usize {
-    input
-        .iter()
-        .map(|group_answers| Group::from(group_answers).len())
-        .sum()
-}
-
-fn part2(input: &[String]) -> usize {
-    input
-        .iter()
-        .map(|group_answers| Group::from(group_answers).all_yes())
-        .sum()
-}
+use day6::Day6;
+use utils::input_read::InputError;
+use utils::solution::Solution;
 
 #[cfg(not(tarpaulin))]
-fn main() {
-    let input = input_read::read_into_string_groups("input").expect("failed to read input file");
-    let part1_result = part1(&input);
+fn main() -> Result<(), InputError> {
+    let input = Day6::parse_input("input")?;
+
+    let part1_result = Day6::part1(&input).expect("failed to solve part1");
     println!("Part 1 result is {}", part1_result);
 
-    let part2_result = part2(&input);
+    let part2_result = Day6::part2(&input).expect("failed to solve part2");
     println!("Part 2 result is {}", part2_result);
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn part1_sample_input() {
-        let input = vec![
-            "abc".to_string(),
-            "a
-b
-c"
-            .to_string(),
-            "ab
-ac"
-            .to_string(),
-            "a
-a
-a
-a"
-            .to_string(),
-            "b".to_string(),
-        ];
-
-        let expected = 11;
-
-        assert_eq!(expected, part1(&input))
-    }
-
-    #[test]
-    fn part2_sample_input() {
-        let input = vec![
-            "abc".to_string(),
-            "a
-b
-c"
-            .to_string(),
-            "ab
-ac"
-            .to_string(),
-            "a
-a
-a
-a"
-            .to_string(),
-            "b".to_string(),
-        ];
-
-        let expected = 6;
 
-        assert_eq!(expected, part2(&input))
-    }
+    Ok(())
 }
@@ -0,0 +1,114 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::graph::BagGraph;
+use serde_json::{json, Value};
+use std::fmt::Write;
+
+// names can't break out of the quoted DOT identifiers
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl BagGraph {
+    /// Renders the rules as a Graphviz digraph, with edges going from the outer bags
+    /// to the bags directly inside them and labelled with their counts.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph bags {\n");
+        for name in self.bag_names() {
+            writeln!(dot, "    {};", quote(name)).unwrap();
+        }
+        for id in self.ids() {
+            for &(count, child) in &self.bag(id).contents {
+                writeln!(
+                    dot,
+                    "    {} -> {} [label={}];",
+                    quote(self.name(id)),
+                    quote(self.name(child)),
+                    count
+                )
+                .unwrap();
+            }
+        }
+        dot.push('}');
+        dot
+    }
+
+    /// Renders the rules as a JSON array of bags, each with the list of bags directly inside it.
+    pub fn to_json(&self) -> String {
+        let bags: Vec<Value> = self
+            .ids()
+            .map(|id| {
+                let contents: Vec<Value> = self
+                    .bag(id)
+                    .contents
+                    .iter()
+                    .map(|&(count, child)| json!({ "count": count, "bag": self.name(child) }))
+                    .collect();
+                json!({ "name": self.name(id), "contains": contents })
+            })
+            .collect();
+
+        serde_json::to_string_pretty(&bags).expect("bag graph is always serializable")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_graph() -> BagGraph {
+        let mut graph = BagGraph::new();
+        graph.insert_rule(
+            "shiny gold".to_string(),
+            vec![
+                (1, "dark olive".to_string()),
+                (2, "vibrant \"plum\"".to_string()),
+            ],
+        );
+        graph.insert_rule("dark olive".to_string(), vec![]);
+        graph
+    }
+
+    #[test]
+    fn dot_export() {
+        let expected = r#"digraph bags {
+    "shiny gold";
+    "dark olive";
+    "vibrant \"plum\"";
+    "shiny gold" -> "dark olive" [label=1];
+    "shiny gold" -> "vibrant \"plum\"" [label=2];
+}"#;
+
+        assert_eq!(expected, small_graph().to_dot());
+    }
+
+    #[test]
+    fn json_export() {
+        let exported: Value = serde_json::from_str(&small_graph().to_json()).unwrap();
+        let expected = json!([
+            {
+                "name": "shiny gold",
+                "contains": [
+                    { "count": 1, "bag": "dark olive" },
+                    { "count": 2, "bag": "vibrant \"plum\"" }
+                ]
+            },
+            { "name": "dark olive", "contains": [] },
+            { "name": "vibrant \"plum\"", "contains": [] }
+        ]);
+
+        assert_eq!(expected, exported);
+    }
+}
@@ -0,0 +1,510 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::interner::{BagId, Interner};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fmt::{self, Display, Formatter};
+use std::iter;
+
+pub(crate) type BagInnerRaw = (usize, String);
+
+/// Chain of bags that end up containing themselves. The first bag is repeated at the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainmentCycle(pub Vec<String>);
+
+impl Display for ContainmentCycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "bags contain each other: {}", self.0.join(" -> "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BagGraphError {
+    UnknownBag(String),
+    Cycle(ContainmentCycle),
+    /// Counting the bags nested within the bag does not fit in a `usize`.
+    Overflow(String),
+}
+
+impl From<ContainmentCycle> for BagGraphError {
+    fn from(cycle: ContainmentCycle) -> Self {
+        BagGraphError::Cycle(cycle)
+    }
+}
+
+impl Display for BagGraphError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BagGraphError::UnknownBag(name) => write!(f, "there are no rules for '{}' bags", name),
+            BagGraphError::Cycle(cycle) => write!(f, "{}", cycle),
+            BagGraphError::Overflow(name) => {
+                write!(f, "'{}' bags contain too many other bags to count", name)
+            }
+        }
+    }
+}
+
+// the name of each bag lives in the interner under the same id
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Bag {
+    // each bag can have multiple other bags inside it, alongside their counts
+    pub(crate) contents: Vec<(usize, BagId)>,
+    // and can be directly inside multiple other bags
+    pub(crate) containers: Vec<BagId>,
+}
+
+/// Bag rules with the bags kept in an arena indexed by their interned names.
+#[derive(Debug, Clone, Default)]
+pub struct BagGraph {
+    names: Interner,
+    bags: Vec<Bag>,
+}
+
+impl BagGraph {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn get_or_insert(&mut self, bag_name: &str) -> BagId {
+        let id = self.names.intern(bag_name);
+        // freshly interned names always get the next free slot
+        if id.index() == self.bags.len() {
+            self.bags.push(Bag::default());
+        }
+        id
+    }
+
+    pub fn insert_rule(&mut self, bag_name: String, inner: Vec<BagInnerRaw>) {
+        let parent = self.get_or_insert(&bag_name);
+
+        // it can't have been set before
+        debug_assert!(self.bags[parent.index()].contents.is_empty());
+
+        for (inner_bag_count, inner_bag_name) in inner {
+            let child = self.get_or_insert(&inner_bag_name);
+            self.bags[parent.index()]
+                .contents
+                .push((inner_bag_count, child));
+            self.bags[child.index()].containers.push(parent);
+        }
+    }
+
+    /// Looks up the id of the bag with the given name.
+    pub fn id(&self, bag_name: &str) -> Result<BagId, BagGraphError> {
+        self.names
+            .lookup(bag_name)
+            .ok_or_else(|| BagGraphError::UnknownBag(bag_name.to_owned()))
+    }
+
+    pub fn name(&self, id: BagId) -> &str {
+        self.names.resolve(id)
+    }
+
+    pub(crate) fn bag(&self, id: BagId) -> &Bag {
+        &self.bags[id.index()]
+    }
+
+    pub fn len(&self) -> usize {
+        self.bags.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bags.is_empty()
+    }
+
+    /// Ids of all the bags, in the order they were first mentioned in the rules.
+    pub fn ids(&self) -> impl Iterator<Item = BagId> {
+        (0..self.bags.len()).map(BagId)
+    }
+
+    /// Names of all the bags, in the order they were first mentioned in the rules.
+    pub fn bag_names(&self) -> impl Iterator<Item = &str> {
+        self.ids().map(move |id| self.name(id))
+    }
+
+    /// Bags directly inside the bag alongside their counts.
+    pub fn contents(&self, bag_name: &str) -> Result<Vec<(usize, &str)>, BagGraphError> {
+        Ok(self
+            .bag(self.id(bag_name)?)
+            .contents
+            .iter()
+            .map(|&(count, child)| (count, self.name(child)))
+            .collect())
+    }
+
+    // names of all bags reachable from the starting one by repeatedly following the edges
+    fn reachable<'a, F, I>(&'a self, start: BagId, neighbours: F) -> BTreeSet<&'a str>
+    where
+        F: Fn(&'a Bag) -> I,
+        I: Iterator<Item = BagId>,
+    {
+        let mut visited = vec![false; self.bags.len()];
+        let mut queue: VecDeque<_> = neighbours(self.bag(start)).collect();
+        let mut reached = BTreeSet::new();
+
+        while let Some(id) = queue.pop_front() {
+            if !visited[id.index()] {
+                visited[id.index()] = true;
+                reached.insert(self.name(id));
+                queue.extend(neighbours(self.bag(id)));
+            }
+        }
+
+        reached
+    }
+
+    /// All bags that eventually contain the bag.
+    pub fn ancestors(&self, bag_name: &str) -> Result<BTreeSet<&str>, BagGraphError> {
+        let start = self.id(bag_name)?;
+        Ok(self.reachable(start, |bag| bag.containers.iter().copied()))
+    }
+
+    /// All bags that are eventually contained within the bag.
+    pub fn descendants(&self, bag_name: &str) -> Result<BTreeSet<&str>, BagGraphError> {
+        let start = self.id(bag_name)?;
+        Ok(self.reachable(start, |bag| bag.contents.iter().map(|&(_, child)| child)))
+    }
+
+    /// Total number of bags nested within the bag.
+    pub fn total_contained(&self, bag_name: &str) -> Result<usize, BagGraphError> {
+        let start = self.id(bag_name)?;
+        Evaluation::new(self, total_of_contents).value(start)
+    }
+
+    /// Total number of nested bags for every bag in the graph, sharing the work between them.
+    pub fn all_totals_contained(&self) -> Result<HashMap<&str, usize>, BagGraphError> {
+        let mut evaluation = Evaluation::new(self, total_of_contents);
+        self.ids()
+            .map(|id| Ok((self.name(id), evaluation.value(id)?)))
+            .collect()
+    }
+
+    /// Length of the longest chain of bags nested within the bag.
+    pub fn depth(&self, bag_name: &str) -> Result<usize, BagGraphError> {
+        let start = self.id(bag_name)?;
+        Evaluation::new(self, |contents: &[(usize, usize)]| {
+            contents
+                .iter()
+                .map(|(_, depth)| depth.checked_add(1))
+                .try_fold(0, |deepest, depth| Some(deepest.max(depth?)))
+        })
+        .value(start)
+    }
+
+    /// Finds any chain of bags that contain themselves, making their contents infinite.
+    pub fn find_cycle(&self) -> Option<ContainmentCycle> {
+        let mut evaluation = Evaluation::new(self, |_: &[(usize, ())]| Some(()));
+        self.ids().find_map(|id| match evaluation.value(id) {
+            Err(BagGraphError::Cycle(cycle)) => Some(cycle),
+            _ => None,
+        })
+    }
+}
+
+fn total_of_contents(contents: &[(usize, usize)]) -> Option<usize> {
+    contents.iter().try_fold(0usize, |sum, &(count, total)| {
+        total.checked_add(1)?.checked_mul(count)?.checked_add(sum)
+    })
+}
+
+#[derive(Clone, Copy)]
+enum Visit<T> {
+    InProgress,
+    Done(T),
+}
+
+// memoised bottom-up computation of a value for each bag from the values of the bags inside it,
+// with `None` from the combining function signalling an overflow;
+// it should not be used anymore once an error got reported
+struct Evaluation<'a, T, F> {
+    graph: &'a BagGraph,
+    combine: F,
+    visits: Vec<Option<Visit<T>>>,
+    // bags being evaluated alongside the index of the next of their contents to look at,
+    // kept explicitly as the chains of bags can be arbitrarily long
+    path: Vec<(BagId, usize)>,
+}
+
+impl<'a, T, F> Evaluation<'a, T, F>
+where
+    T: Copy,
+    F: Fn(&[(usize, T)]) -> Option<T>,
+{
+    fn new(graph: &'a BagGraph, combine: F) -> Self {
+        Evaluation {
+            graph,
+            combine,
+            visits: vec![None; graph.bags.len()],
+            path: Vec::new(),
+        }
+    }
+
+    fn done(&self, id: BagId) -> Option<T> {
+        match self.visits[id.index()] {
+            Some(Visit::Done(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn value(&mut self, id: BagId) -> Result<T, BagGraphError> {
+        match self.visits[id.index()] {
+            Some(Visit::Done(value)) => return Ok(value),
+            Some(Visit::InProgress) => return Err(self.cycle_to(id).into()),
+            None => (),
+        }
+
+        let graph = self.graph;
+        self.visits[id.index()] = Some(Visit::InProgress);
+        self.path.push((id, 0));
+
+        while let Some((current, next)) = self.path.last_mut() {
+            let (current, contents) = (*current, &graph.bag(*current).contents);
+            if let Some(&(_, child)) = contents.get(*next) {
+                *next += 1;
+                match self.visits[child.index()] {
+                    Some(Visit::Done(_)) => (),
+                    Some(Visit::InProgress) => return Err(self.cycle_to(child).into()),
+                    None => {
+                        self.visits[child.index()] = Some(Visit::InProgress);
+                        self.path.push((child, 0));
+                    }
+                }
+                continue;
+            }
+
+            // everything inside the bag has been evaluated by now
+            let contents: Vec<_> = contents
+                .iter()
+                .map(|&(count, child)| (count, self.done(child).unwrap()))
+                .collect();
+            let value = (self.combine)(&contents)
+                .ok_or_else(|| BagGraphError::Overflow(graph.name(current).to_owned()))?;
+            self.visits[current.index()] = Some(Visit::Done(value));
+            self.path.pop();
+        }
+
+        Ok(self.done(id).unwrap())
+    }
+
+    // the bag in progress must be somewhere on the current path
+    fn cycle_to(&self, id: BagId) -> ContainmentCycle {
+        let start = self
+            .path
+            .iter()
+            .position(|&(visited, _)| visited == id)
+            .expect("bag in progress is not on the path");
+
+        ContainmentCycle(
+            self.path[start..]
+                .iter()
+                .map(|&(bag, _)| bag)
+                .chain(iter::once(id))
+                .map(|bag| self.graph.name(bag).to_owned())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_graph;
+
+    fn sample_graph() -> BagGraph {
+        let input: Vec<_> = [
+            "light red bags contain 1 bright white bag, 2 muted yellow bags.",
+            "dark orange bags contain 3 bright white bags, 4 muted yellow bags.",
+            "bright white bags contain 1 shiny gold bag.",
+            "muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.",
+            "shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.",
+            "dark olive bags contain 3 faded blue bags, 4 dotted black bags.",
+            "vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.",
+            "faded blue bags contain no other bags.",
+            "dotted black bags contain no other bags.",
+        ]
+        .iter()
+        .map(|rule| rule.to_string())
+        .collect();
+
+        build_graph(&input)
+    }
+
+    fn cyclic_graph() -> BagGraph {
+        let input: Vec<_> = [
+            "shiny gold bags contain 1 dark olive bag.",
+            "dark olive bags contain 2 vibrant plum bags, 1 faded blue bag.",
+            "vibrant plum bags contain 1 shiny gold bag.",
+            "faded blue bags contain no other bags.",
+        ]
+        .iter()
+        .map(|rule| rule.to_string())
+        .collect();
+
+        build_graph(&input)
+    }
+
+    #[test]
+    fn ancestors_and_descendants() {
+        let graph = sample_graph();
+
+        let ancestors: Vec<_> = graph.ancestors("shiny gold").unwrap().into_iter().collect();
+        assert_eq!(
+            vec!["bright white", "dark orange", "light red", "muted yellow"],
+            ancestors
+        );
+        assert!(graph.ancestors("light red").unwrap().is_empty());
+
+        let descendants: Vec<_> = graph
+            .descendants("shiny gold")
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(
+            vec!["dark olive", "dotted black", "faded blue", "vibrant plum"],
+            descendants
+        );
+        assert!(graph.descendants("faded blue").unwrap().is_empty());
+    }
+
+    #[test]
+    fn nested_totals_and_depth() {
+        let graph = sample_graph();
+
+        assert_eq!(Ok(32), graph.total_contained("shiny gold"));
+        assert_eq!(Ok(0), graph.total_contained("dotted black"));
+        assert_eq!(Ok(7), graph.total_contained("dark olive"));
+
+        let totals = graph.all_totals_contained().unwrap();
+        assert_eq!(graph.len(), totals.len());
+        assert_eq!(32, totals["shiny gold"]);
+        assert_eq!(2 * (1 + 32) + 9, totals["muted yellow"]);
+
+        assert_eq!(Ok(2), graph.depth("shiny gold"));
+        assert_eq!(Ok(4), graph.depth("light red"));
+        assert_eq!(Ok(0), graph.depth("faded blue"));
+        assert_eq!(None, graph.find_cycle());
+    }
+
+    #[test]
+    fn cycles_are_detected() {
+        let graph = cyclic_graph();
+        let expected = ContainmentCycle(vec![
+            "shiny gold".to_string(),
+            "dark olive".to_string(),
+            "vibrant plum".to_string(),
+            "shiny gold".to_string(),
+        ]);
+
+        assert_eq!(Some(expected.clone()), graph.find_cycle());
+        assert_eq!(
+            Err(BagGraphError::Cycle(expected)),
+            graph.total_contained("shiny gold")
+        );
+        assert!(graph.depth("vibrant plum").is_err());
+        assert!(graph.all_totals_contained().is_err());
+
+        // bags outside the cycle are still fine
+        assert_eq!(Ok(0), graph.total_contained("faded blue"));
+
+        // and so are the queries that don't need to count anything
+        assert!(graph
+            .ancestors("shiny gold")
+            .unwrap()
+            .contains("shiny gold"));
+        assert_eq!(3, graph.ancestors("faded blue").unwrap().len());
+    }
+
+    fn chain_graph(length: usize, count: usize) -> BagGraph {
+        let input: Vec<_> = (0..length)
+            .map(|i| format!("bag {} bags contain {} bag {} bags.", i, count, i + 1))
+            .chain(iter::once(format!(
+                "bag {} bags contain no other bags.",
+                length
+            )))
+            .collect();
+
+        build_graph(&input)
+    }
+
+    #[test]
+    fn long_chains_do_not_overflow_the_stack() {
+        let graph = chain_graph(100_000, 1);
+
+        assert_eq!(Ok(100_000), graph.total_contained("bag 0"));
+        assert_eq!(Ok(100_000), graph.depth("bag 0"));
+        assert_eq!(None, graph.find_cycle());
+    }
+
+    #[test]
+    fn overflowing_totals_are_reported() {
+        let graph = chain_graph(10, 1000);
+
+        assert_eq!(
+            Err(BagGraphError::Overflow("bag 3".to_string())),
+            graph.total_contained("bag 0")
+        );
+        assert_eq!(
+            Err(BagGraphError::Overflow("bag 3".to_string())),
+            graph.all_totals_contained()
+        );
+        assert_eq!(Ok(1_001_001_001_001_000), graph.total_contained("bag 5"));
+        assert_eq!(Ok(10), graph.depth("bag 0"));
+    }
+
+    #[test]
+    fn unknown_bags_are_reported() {
+        let graph = sample_graph();
+        let unknown = BagGraphError::UnknownBag("mirrored teal".to_string());
+
+        assert_eq!(Err(unknown.clone()), graph.id("mirrored teal"));
+        assert_eq!(Err(unknown.clone()), graph.contents("mirrored teal"));
+        assert_eq!(Err(unknown.clone()), graph.ancestors("mirrored teal"));
+        assert_eq!(Err(unknown.clone()), graph.descendants("mirrored teal"));
+        assert_eq!(Err(unknown.clone()), graph.total_contained("mirrored teal"));
+        assert_eq!(Err(unknown), graph.depth("mirrored teal"));
+    }
+
+    #[test]
+    fn bags_are_looked_up_by_interned_ids() {
+        let graph = sample_graph();
+        let gold = graph.id("shiny gold").unwrap();
+
+        assert_eq!("shiny gold", graph.name(gold));
+        assert_eq!(
+            vec![(1, "dark olive"), (2, "vibrant plum")],
+            graph.contents("shiny gold").unwrap()
+        );
+        assert_eq!(graph.len(), graph.ids().count());
+    }
+
+    #[test]
+    fn graph_can_be_shared_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<BagGraph>();
+
+        let graph = &sample_graph();
+        let totals: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = ["shiny gold", "dark olive"]
+                .iter()
+                .map(|&name| scope.spawn(move || graph.total_contained(name)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        assert_eq!(vec![Ok(32), Ok(7)], totals);
+    }
+}
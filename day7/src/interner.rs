@@ -0,0 +1,75 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Handle to an interned bag name, doubling as the index of the bag within the graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BagId(pub(crate) usize);
+
+impl BagId {
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Stores each distinct bag name once and hands out dense ids for them.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Interner {
+    names: Vec<Arc<str>>,
+    ids: HashMap<Arc<str>, BagId>,
+}
+
+impl Interner {
+    pub(crate) fn intern(&mut self, name: &str) -> BagId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = BagId(self.names.len());
+        let name: Arc<str> = Arc::from(name);
+        self.names.push(Arc::clone(&name));
+        self.ids.insert(name, id);
+        id
+    }
+
+    pub(crate) fn lookup(&self, name: &str) -> Option<BagId> {
+        self.ids.get(name).copied()
+    }
+
+    pub(crate) fn resolve(&self, id: BagId) -> &str {
+        &self.names[id.0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_interned_once() {
+        let mut interner = Interner::default();
+        let gold = interner.intern("shiny gold");
+        let olive = interner.intern("dark olive");
+
+        assert_ne!(gold, olive);
+        assert_eq!(gold, interner.intern("shiny gold"));
+        assert_eq!(2, interner.names.len());
+
+        assert_eq!("dark olive", interner.resolve(olive));
+        assert_eq!(Some(gold), interner.lookup("shiny gold"));
+        assert_eq!(None, interner.lookup("vibrant plum"));
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::Infallible;
+use std::path::Path;
+use utils::input_read::{self, InputError};
+use utils::solution::Solution;
+
+use graph::BagInnerRaw;
+pub use graph::{BagGraph, BagGraphError, ContainmentCycle};
+pub use interner::BagId;
+
+mod export;
+mod graph;
+mod interner;
+
+const EMPTY_BAG: &str = "no other";
+const TARGET_BAG: &str = "shiny gold";
+
+fn into_count_and_name(raw_bag: &str) -> Option<BagInnerRaw> {
+    if raw_bag.starts_with(EMPTY_BAG) {
+        return None;
+    }
+    let whitespace_split: Vec<_> = raw_bag.split_ascii_whitespace().collect();
+    // we must at least have a count and name
+    assert!(whitespace_split.len() >= 2);
+    let count = whitespace_split[0]
+        .parse()
+        .expect("failed to parse bag count!");
+
+    // final chunk is word 'bag' or 'bags' and we don't care about it
+    let name = whitespace_split[1..whitespace_split.len() - 1].join(" ");
+    Some((count, name))
+}
+
+fn parse_rule(rule: &str) -> (String, Vec<BagInnerRaw>) {
+    let split: Vec<_> = rule.split("contain").collect();
+    let bag_name = split[0].trim().trim_end_matches(" bags").to_owned();
+
+    let inner_bags = split[1].split([',', '.']);
+    let raw_inner = inner_bags
+        .map(str::trim)
+        .filter(|bag| !bag.is_empty())
+        .filter_map(into_count_and_name)
+        .collect();
+
+    (bag_name, raw_inner)
+}
+
+pub fn build_graph(input: &[String]) -> BagGraph {
+    let mut graph = BagGraph::new();
+    input
+        .iter()
+        .map(|rule| parse_rule(rule))
+        .for_each(|rule| graph.insert_rule(rule.0, rule.1));
+    graph
+}
+
+pub fn part1(input: &[String]) -> Option<usize> {
+    build_graph(input)
+        .ancestors(TARGET_BAG)
+        .map(|ancestors| ancestors.len())
+        .ok()
+}
+
+pub fn part2(input: &[String]) -> Option<usize> {
+    build_graph(input).total_contained(TARGET_BAG).ok()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<String>;
+    type ParseError = Infallible;
+    type Part1Output = usize;
+    type Part2Output = usize;
+
+    fn parse_input<P: AsRef<Path>>(path: P) -> Result<Self::Input, InputError<Self::ParseError>> {
+        input_read::read_line_input(path)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Part1Output> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2Output> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_part1_input() {
+        let input = vec![
+            "light red bags contain 1 bright white bag, 2 muted yellow bags.".to_string(),
+            "dark orange bags contain 3 bright white bags, 4 muted yellow bags.".to_string(),
+            "bright white bags contain 1 shiny gold bag.".to_string(),
+            "muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.".to_string(),
+            "shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.".to_string(),
+            "dark olive bags contain 3 faded blue bags, 4 dotted black bags.".to_string(),
+            "vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.".to_string(),
+            "faded blue bags contain no other bags.".to_string(),
+            "dotted black bags contain no other bags.".to_string(),
+        ];
+
+        let expected = 4;
+
+        assert_eq!(expected, part1(&input).unwrap());
+    }
+
+    #[test]
+    fn sample_part2_input1() {
+        let input = vec![
+            "light red bags contain 1 bright white bag, 2 muted yellow bags.".to_string(),
+            "dark orange bags contain 3 bright white bags, 4 muted yellow bags.".to_string(),
+            "bright white bags contain 1 shiny gold bag.".to_string(),
+            "muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.".to_string(),
+            "shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.".to_string(),
+            "dark olive bags contain 3 faded blue bags, 4 dotted black bags.".to_string(),
+            "vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.".to_string(),
+            "faded blue bags contain no other bags.".to_string(),
+            "dotted black bags contain no other bags.".to_string(),
+        ];
+
+        let expected = 32;
+
+        assert_eq!(expected, part2(&input).unwrap());
+    }
+
+    #[test]
+    fn sample_part2_input2() {
+        let input = vec![
+            "shiny gold bags contain 2 dark red bags.".to_string(),
+            "dark red bags contain 2 dark orange bags.".to_string(),
+            "dark orange bags contain 2 dark yellow bags.".to_string(),
+            "dark yellow bags contain 2 dark green bags.".to_string(),
+            "dark green bags contain 2 dark blue bags.".to_string(),
+            "dark blue bags contain 2 dark violet bags.".to_string(),
+            "dark violet bags contain no other bags.".to_string(),
+        ];
+
+        let expected = 126;
+
+        assert_eq!(expected, part2(&input).unwrap());
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::instruction::{Instruction, Opcode, Operand};
+use std::collections::VecDeque;
+use std::fmt::{self, Display, Formatter};
+
+/// Control flow graph of a program. Nodes are the instructions and an extra node, placed right
+/// after the last instruction, representing termination of the program.
+/// Conditional jumps are assumed to be able to go either way.
+pub struct ControlFlowGraph {
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+    // successor of each `nop` and `jmp` if it got swapped with the other one
+    flipped_successors: Vec<Option<usize>>,
+    out_of_bounds_jumps: Vec<(usize, isize)>,
+    has_conditional_jumps: bool,
+}
+
+impl ControlFlowGraph {
+    pub fn new(program: &[Instruction]) -> Self {
+        let termination = program.len();
+        let target = |index: usize, offset: &Operand| match offset {
+            Operand::Immediate(offset) => index as isize + offset,
+            Operand::Register(_) => unreachable!("jump offsets are always immediate"),
+        };
+        let in_bounds = |target: isize| {
+            if target >= 0 && target as usize <= termination {
+                Some(target as usize)
+            } else {
+                None
+            }
+        };
+
+        let mut successors = vec![Vec::new(); termination + 1];
+        let mut flipped_successors = vec![None; termination];
+        let mut out_of_bounds_jumps = Vec::new();
+        let mut has_conditional_jumps = false;
+
+        for (index, instruction) in program.iter().enumerate() {
+            let mut targets = Vec::with_capacity(2);
+            match instruction.opcode() {
+                Opcode::Halt => targets.push(termination as isize),
+                Opcode::Jump => {
+                    targets.push(target(index, &instruction.operands()[0]));
+                    flipped_successors[index] = Some(index + 1);
+                }
+                Opcode::Nop => {
+                    targets.push(index as isize + 1);
+                    flipped_successors[index] =
+                        in_bounds(target(index, &instruction.operands()[0]));
+                }
+                Opcode::JumpIfZero | Opcode::JumpIfNotZero => {
+                    has_conditional_jumps = true;
+                    targets.push(index as isize + 1);
+                    targets.push(target(index, &instruction.operands()[1]));
+                }
+                Opcode::Acc | Opcode::Add | Opcode::Mul | Opcode::Out => {
+                    targets.push(index as isize + 1)
+                }
+            }
+
+            for target in targets {
+                match in_bounds(target) {
+                    Some(successor) if !successors[index].contains(&successor) => {
+                        successors[index].push(successor)
+                    }
+                    Some(_) => (),
+                    None => out_of_bounds_jumps.push((index, target)),
+                }
+            }
+        }
+
+        let mut predecessors = vec![Vec::new(); termination + 1];
+        for (index, index_successors) in successors.iter().enumerate() {
+            for &successor in index_successors {
+                predecessors[successor].push(index)
+            }
+        }
+
+        ControlFlowGraph {
+            successors,
+            predecessors,
+            flipped_successors,
+            out_of_bounds_jumps,
+            has_conditional_jumps,
+        }
+    }
+
+    /// Index of the node representing termination of the program.
+    pub fn termination_index(&self) -> usize {
+        self.successors.len() - 1
+    }
+
+    // breadth-first search over the provided edges
+    fn visit_from(start: usize, edges: &[Vec<usize>]) -> Vec<bool> {
+        let mut visited = vec![false; edges.len()];
+        let mut queue = VecDeque::new();
+        visited[start] = true;
+        queue.push_back(start);
+
+        while let Some(node) = queue.pop_front() {
+            for &next in edges[node].iter() {
+                if !visited[next] {
+                    visited[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        visited
+    }
+
+    fn reachable(&self) -> Vec<bool> {
+        Self::visit_from(0, &self.successors)
+    }
+
+    fn reaching_termination(&self) -> Vec<bool> {
+        Self::visit_from(self.termination_index(), &self.predecessors)
+    }
+
+    /// Instructions that can never be executed when starting from the first one.
+    pub fn unreachable_instructions(&self) -> Vec<usize> {
+        let reachable = self.reachable();
+        (0..self.termination_index())
+            .filter(|&index| !reachable[index])
+            .collect()
+    }
+
+    /// Instructions from which it is possible to get to the termination of the program.
+    pub fn instructions_reaching_termination(&self) -> Vec<usize> {
+        let reaching = self.reaching_termination();
+        (0..self.termination_index())
+            .filter(|&index| reaching[index])
+            .collect()
+    }
+
+    /// Jumps that would move execution outside the program alongside their targets.
+    pub fn out_of_bounds_jumps(&self) -> &[(usize, isize)] {
+        &self.out_of_bounds_jumps
+    }
+
+    /// Finds the `nop` or `jmp` whose swap with the other one makes the looping program
+    /// terminate. The control flow has to be fully determined by the program itself,
+    /// so it only works for programs without conditional jumps.
+    pub fn terminating_flip(&self) -> Option<usize> {
+        if self.has_conditional_jumps {
+            return None;
+        }
+
+        // flipping an instruction on the execution path is only useful if the execution
+        // continued from an instruction that is already known to lead to termination.
+        // Note that the continuation can't go through the flipped instruction itself,
+        // as otherwise the original program would have terminated as well
+        let reaching_termination = self.reaching_termination();
+        let mut visited = vec![false; self.termination_index()];
+        let mut current = 0;
+
+        while current != self.termination_index() && !visited[current] {
+            visited[current] = true;
+            if let Some(flipped) = self.flipped_successors[current] {
+                if reaching_termination[flipped] {
+                    return Some(current);
+                }
+            }
+
+            // without conditional jumps every instruction has at most a single successor
+            current = *self.successors[current].first()?;
+        }
+
+        None
+    }
+}
+
+/// Potential problem found in a program.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Lint {
+    UnreachableInstruction(usize),
+    JumpOutOfBounds {
+        index: usize,
+        target: isize,
+    },
+    /// There is no way to get from the first instruction to the end of the program.
+    CannotTerminate,
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Lint::UnreachableInstruction(index) => {
+                write!(f, "instruction {} is never executed", index)
+            }
+            Lint::JumpOutOfBounds { index, target } => write!(
+                f,
+                "instruction {} jumps outside the program (to {})",
+                index, target
+            ),
+            Lint::CannotTerminate => write!(f, "the program can never terminate"),
+        }
+    }
+}
+
+/// Statically checks the program for potential problems.
+pub fn lint(program: &[Instruction]) -> Vec<Lint> {
+    let graph = ControlFlowGraph::new(program);
+
+    let mut lints: Vec<_> = graph
+        .unreachable_instructions()
+        .into_iter()
+        .map(Lint::UnreachableInstruction)
+        .collect();
+
+    lints.extend(
+        graph
+            .out_of_bounds_jumps()
+            .iter()
+            .map(|&(index, target)| Lint::JumpOutOfBounds { index, target }),
+    );
+
+    if !graph.reaching_termination()[0] {
+        lints.push(Lint::CannotTerminate)
+    }
+
+    lints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler::assemble;
+
+    fn sample_program() -> Vec<Instruction> {
+        assemble("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6").unwrap()
+    }
+
+    #[test]
+    fn finds_the_terminating_flip() {
+        assert_eq!(
+            Some(7),
+            ControlFlowGraph::new(&sample_program()).terminating_flip()
+        );
+    }
+
+    #[test]
+    fn reachability() {
+        let graph = ControlFlowGraph::new(&sample_program());
+
+        assert_eq!(vec![5, 8], graph.unreachable_instructions());
+        assert_eq!(vec![8], graph.instructions_reaching_termination());
+    }
+
+    #[test]
+    fn conditional_jumps_can_go_either_way() {
+        let program = assemble("add a +1\nloop: jnz a loop\nhalt\nout a").unwrap();
+        let graph = ControlFlowGraph::new(&program);
+
+        assert_eq!(vec![3], graph.unreachable_instructions());
+        assert_eq!(vec![0, 1, 2, 3], graph.instructions_reaching_termination());
+        assert_eq!(None, graph.terminating_flip());
+    }
+
+    #[test]
+    fn linting() {
+        let program = assemble("start: acc +1\njmp start\njmp -10").unwrap();
+
+        assert_eq!(
+            vec![
+                Lint::UnreachableInstruction(2),
+                Lint::JumpOutOfBounds {
+                    index: 2,
+                    target: -8
+                },
+                Lint::CannotTerminate
+            ],
+            lint(&program)
+        );
+        assert!(lint(&assemble("acc +1\nhalt").unwrap()).is_empty());
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::instruction::{is_identifier, Instruction, InvalidInstruction};
+use std::collections::HashMap;
+
+const LABEL_SUFFIX: char = ':';
+const COMMENT_START: char = ';';
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum AssemblyErrorKind {
+    InvalidInstruction(InvalidInstruction),
+    InvalidLabel(String),
+    DuplicateLabel(String),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct AssemblyError {
+    /// 1-based line of the source the error occurred at.
+    pub line: usize,
+    pub kind: AssemblyErrorKind,
+}
+
+impl AssemblyError {
+    fn new(line: usize, kind: AssemblyErrorKind) -> Self {
+        AssemblyError { line, kind }
+    }
+}
+
+// line of the source that contains an actual instruction
+struct SourceInstruction<'a> {
+    line: usize,
+    raw: &'a str,
+}
+
+/// Translates the source into a program. Apart from the plain instructions, the source might
+/// contain `;` comments and labels, i.e. `name:`, either on their own lines or preceding
+/// an instruction. Labels can be used in place of any jump offset.
+pub fn assemble(source: &str) -> Result<Vec<Instruction>, AssemblyError> {
+    let mut labels = HashMap::new();
+    let mut instructions = Vec::new();
+
+    for (line, raw_line) in source.lines().enumerate() {
+        let line = line + 1;
+        let mut remaining = match raw_line.find(COMMENT_START) {
+            Some(comment_start) => &raw_line[..comment_start],
+            None => raw_line,
+        }
+        .trim();
+
+        if let Some(label_end) = remaining.find(LABEL_SUFFIX) {
+            let label = remaining[..label_end].trim();
+            if !is_identifier(label) {
+                return Err(AssemblyError::new(
+                    line,
+                    AssemblyErrorKind::InvalidLabel(label.to_owned()),
+                ));
+            }
+            if labels.insert(label, instructions.len()).is_some() {
+                return Err(AssemblyError::new(
+                    line,
+                    AssemblyErrorKind::DuplicateLabel(label.to_owned()),
+                ));
+            }
+            remaining = remaining[label_end + 1..].trim();
+        }
+
+        if !remaining.is_empty() {
+            instructions.push(SourceInstruction {
+                line,
+                raw: remaining,
+            })
+        }
+    }
+
+    instructions
+        .iter()
+        .enumerate()
+        .map(|(index, instruction)| {
+            let resolve_label = |label: &str| {
+                labels
+                    .get(label)
+                    .map(|&target| target as isize - index as isize)
+            };
+            Instruction::parse_with_labels(instruction.raw, resolve_label).map_err(|err| {
+                AssemblyError::new(instruction.line, AssemblyErrorKind::InvalidInstruction(err))
+            })
+        })
+        .collect()
+}
+
+/// Translates the program into its canonical text form, with an instruction per line,
+/// that assembles back into the same program.
+pub fn disassemble(program: &[Instruction]) -> String {
+    program
+        .iter()
+        .map(|instruction| format!("{}\n", instruction))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn labels_are_resolved_into_relative_offsets() {
+        let source = "
+            ; counts down from 3
+                add n +3
+            loop: add n -1
+                out n
+                jnz n loop
+                jmp end
+                acc +100
+            end:
+        ";
+
+        let expected: Vec<_> = [
+            "add n +3", "add n -1", "out n", "jnz n -2", "jmp +2", "acc +100",
+        ]
+        .iter()
+        .map(|raw| Instruction::try_from(*raw).unwrap())
+        .collect();
+
+        assert_eq!(expected, assemble(source).unwrap());
+    }
+
+    #[test]
+    fn label_errors_point_at_the_line() {
+        assert_eq!(
+            Err(AssemblyError::new(
+                3,
+                AssemblyErrorKind::DuplicateLabel("a".to_string())
+            )),
+            assemble("a: nop +0\nnop +0\na: nop +0")
+        );
+        assert_eq!(
+            Err(AssemblyError::new(
+                2,
+                AssemblyErrorKind::InvalidInstruction(InvalidInstruction::UnknownLabel(
+                    "b".to_string()
+                ))
+            )),
+            assemble("a: nop +0\njmp b")
+        );
+        assert_eq!(
+            Err(AssemblyError::new(
+                1,
+                AssemblyErrorKind::InvalidLabel("1a".to_string())
+            )),
+            assemble("1a: nop +0")
+        );
+    }
+
+    #[test]
+    fn disassembly_round_trips() {
+        let source = "start: add x +2\nmul x x\njz x start\nout x\nacc -3\nnop -1\nhalt";
+        let program = assemble(source).unwrap();
+
+        let disassembled = disassemble(&program);
+        assert_eq!(
+            "add x +2\nmul x x\njz x -2\nout x\nacc -3\nnop -1\nhalt\n",
+            disassembled
+        );
+        assert_eq!(program, assemble(&disassembled).unwrap());
+    }
+}
@@ -0,0 +1,758 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::instruction::{Instruction, Opcode, Operand, Register};
+use std::collections::{BTreeMap, HashSet};
+
+/// Reason for which the computer has stopped executing the program.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Termination {
+    /// Attempted to execute the instruction right after the last one in the program.
+    Finished { accumulator: isize },
+    /// Executed the `halt` instruction.
+    Halted {
+        accumulator: isize,
+        instruction_pointer: usize,
+    },
+    /// Got into a state it has already been in before, meaning it'd loop forever.
+    LoopDetected {
+        accumulator: isize,
+        instruction_pointer: usize,
+    },
+    /// An instruction has attempted to move execution outside the program.
+    JumpOutOfBounds {
+        accumulator: isize,
+        instruction_pointer: usize,
+        target: isize,
+    },
+    /// The program did not terminate within the configured number of steps.
+    StepLimitExceeded { accumulator: isize, steps: usize },
+    /// An instruction has produced a value that doesn't fit in a register.
+    Overflow {
+        accumulator: isize,
+        instruction_pointer: usize,
+    },
+}
+
+impl Termination {
+    pub fn accumulator(&self) -> isize {
+        match *self {
+            Termination::Finished { accumulator }
+            | Termination::Halted { accumulator, .. }
+            | Termination::LoopDetected { accumulator, .. }
+            | Termination::JumpOutOfBounds { accumulator, .. }
+            | Termination::StepLimitExceeded { accumulator, .. }
+            | Termination::Overflow { accumulator, .. } => accumulator,
+        }
+    }
+
+    /// Whether the program has terminated on its own accord, i.e. by running out of
+    /// instructions or by halting.
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            Termination::Finished { .. } | Termination::Halted { .. }
+        )
+    }
+}
+
+/// Result of attempting to execute a single instruction.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum StepResult {
+    Executed,
+    Terminated(Termination),
+}
+
+/// Result of running the program until something interesting happens.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum RunResult {
+    /// Execution has stopped before executing instruction at the provided index.
+    Breakpoint(usize),
+    Terminated(Termination),
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Breakpoint {
+    Instruction(usize),
+    Opcode(Opcode),
+}
+
+/// Snapshot of the computer registers.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Registers {
+    pub accumulator: isize,
+    pub instruction_pointer: usize,
+    /// Values of all named registers that were written to so far. Remaining ones are 0.
+    pub named: BTreeMap<String, isize>,
+}
+
+impl Registers {
+    pub fn get(&self, register: &Register) -> isize {
+        match register {
+            Register::Accumulator => self.accumulator,
+            Register::Named(name) => self.named.get(name).copied().unwrap_or_default(),
+        }
+    }
+
+    pub fn set(&mut self, register: &Register, value: isize) {
+        match register {
+            Register::Accumulator => self.accumulator = value,
+            Register::Named(name) => {
+                self.named.insert(name.clone(), value);
+            }
+        }
+    }
+
+    fn read(&self, operand: &Operand) -> isize {
+        match operand {
+            Operand::Immediate(value) => *value,
+            Operand::Register(register) => self.get(register),
+        }
+    }
+
+    // returns `None` if the new value couldn't be computed, leaving the register intact
+    fn modify<F>(&mut self, operand: &Operand, value: isize, f: F) -> Option<()>
+    where
+        F: Fn(isize, isize) -> Option<isize>,
+    {
+        match operand {
+            Operand::Register(register) => {
+                let new_value = f(self.get(register), value)?;
+                self.set(register, new_value);
+                Some(())
+            }
+            Operand::Immediate(_) => panic!("attempted to write into an immediate value"),
+        }
+    }
+}
+
+/// Record of a single executed instruction.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TraceEntry {
+    pub step: usize,
+    pub address: usize,
+    pub instruction: Instruction,
+    /// State of the registers after the instruction got executed.
+    pub registers: Registers,
+}
+
+type Hook = Box<dyn FnMut(&TraceEntry)>;
+
+enum LoopDetector {
+    // without conditional jumps the control flow does not depend on the registers,
+    // so getting back to any instruction means we're in a loop
+    Revisit(Vec<bool>),
+    // otherwise the entire state has to repeat
+    State(HashSet<Registers>),
+}
+
+impl LoopDetector {
+    fn for_program(program: &[Instruction]) -> Self {
+        let has_conditional_jumps = program.iter().any(|instruction| {
+            matches!(
+                instruction.opcode(),
+                Opcode::JumpIfZero | Opcode::JumpIfNotZero
+            )
+        });
+
+        if has_conditional_jumps {
+            LoopDetector::State(HashSet::new())
+        } else {
+            LoopDetector::Revisit(vec![false; program.len()])
+        }
+    }
+
+    fn has_seen(&self, registers: &Registers) -> bool {
+        match self {
+            LoopDetector::Revisit(executed) => executed[registers.instruction_pointer],
+            LoopDetector::State(states) => states.contains(registers),
+        }
+    }
+
+    fn mark_seen(&mut self, registers: &Registers) {
+        match self {
+            LoopDetector::Revisit(executed) => executed[registers.instruction_pointer] = true,
+            LoopDetector::State(states) => {
+                states.insert(registers.clone());
+            }
+        }
+    }
+}
+
+pub struct Computer {
+    program: Vec<Instruction>,
+    registers: Registers,
+    steps: usize,
+    output: Vec<isize>,
+    // used to look for cycles
+    loop_detector: LoopDetector,
+
+    step_limit: Option<usize>,
+    breakpoints: Vec<Breakpoint>,
+    // set when execution got paused so that resuming would not trigger the same breakpoint again
+    paused_at: Option<usize>,
+    // once terminated, the computer keeps reporting the same termination until reset
+    terminated: Option<Termination>,
+    trace: Option<Vec<TraceEntry>>,
+    hooks: Vec<Hook>,
+}
+
+impl Computer {
+    pub fn new(program: Vec<Instruction>) -> Computer {
+        Computer {
+            loop_detector: LoopDetector::for_program(&program),
+            program,
+            registers: Default::default(),
+            steps: 0,
+            output: Vec::new(),
+            step_limit: None,
+            breakpoints: Vec::new(),
+            paused_at: None,
+            terminated: None,
+            trace: None,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Resets the execution state. Breakpoints, hooks and the step limit are preserved.
+    pub fn reset(&mut self) {
+        self.registers = Default::default();
+        self.steps = 0;
+        self.output.clear();
+        self.loop_detector = LoopDetector::for_program(&self.program);
+        self.paused_at = None;
+        self.terminated = None;
+        if let Some(trace) = self.trace.as_mut() {
+            trace.clear();
+        }
+    }
+
+    pub fn program(&self) -> &[Instruction] {
+        &self.program
+    }
+
+    /// Edits the instruction at the address, returning `None` if there's no such instruction.
+    /// The execution state is kept, apart from the states visited so far being forgotten,
+    /// as they might not lead into a loop anymore.
+    pub fn edit_instruction<F, R>(&mut self, address: usize, edit: F) -> Option<R>
+    where
+        F: FnOnce(&mut Instruction) -> R,
+    {
+        let result = edit(self.program.get_mut(address)?);
+        self.loop_detector = LoopDetector::for_program(&self.program);
+        Some(result)
+    }
+
+    pub fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    pub fn accumulator(&self) -> isize {
+        self.registers.accumulator
+    }
+
+    pub fn instruction_pointer(&self) -> usize {
+        self.registers.instruction_pointer
+    }
+
+    /// Number of instructions executed so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Values emitted by the `out` instructions.
+    pub fn output(&self) -> &[isize] {
+        &self.output
+    }
+
+    /// Sets the step limit. If the computer has already exceeded the previous one,
+    /// it may continue executing the program.
+    pub fn set_step_limit(&mut self, limit: Option<usize>) {
+        self.step_limit = limit;
+        if let Some(Termination::StepLimitExceeded { .. }) = self.terminated {
+            self.terminated = None;
+        }
+    }
+
+    /// Reason the computer has stopped executing the program, if it did.
+    pub fn termination(&self) -> Option<Termination> {
+        self.terminated
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        if !self.breakpoints.contains(&breakpoint) {
+            self.breakpoints.push(breakpoint)
+        }
+    }
+
+    pub fn remove_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.retain(|existing| *existing != breakpoint)
+    }
+
+    pub fn enable_trace(&mut self) {
+        self.trace.get_or_insert_with(Vec::new);
+    }
+
+    /// Returns all instructions executed since the trace got enabled or the computer reset.
+    pub fn trace(&self) -> Option<&[TraceEntry]> {
+        self.trace.as_deref()
+    }
+
+    /// Registers a callback invoked after every executed instruction.
+    pub fn add_hook<F>(&mut self, hook: F)
+    where
+        F: FnMut(&TraceEntry) + 'static,
+    {
+        self.hooks.push(Box::new(hook))
+    }
+
+    fn terminate(&mut self, termination: Termination) -> Termination {
+        self.terminated = Some(termination);
+        termination
+    }
+
+    fn check_termination(&mut self) -> Option<Termination> {
+        if self.terminated.is_some() {
+            return self.terminated;
+        }
+
+        let termination = self.detect_termination()?;
+        Some(self.terminate(termination))
+    }
+
+    fn detect_termination(&self) -> Option<Termination> {
+        let accumulator = self.registers.accumulator;
+        let instruction_pointer = self.registers.instruction_pointer;
+
+        if instruction_pointer == self.program.len() {
+            return Some(Termination::Finished { accumulator });
+        }
+
+        if self.loop_detector.has_seen(&self.registers) {
+            return Some(Termination::LoopDetected {
+                accumulator,
+                instruction_pointer,
+            });
+        }
+
+        match self.step_limit {
+            Some(limit) if self.steps >= limit => Some(Termination::StepLimitExceeded {
+                accumulator,
+                steps: self.steps,
+            }),
+            _ => None,
+        }
+    }
+
+    fn is_breakpoint(&self, address: usize) -> bool {
+        let opcode = self.program[address].opcode();
+        self.breakpoints.iter().any(|breakpoint| match *breakpoint {
+            Breakpoint::Instruction(index) => index == address,
+            Breakpoint::Opcode(breakpoint_opcode) => breakpoint_opcode == opcode,
+        })
+    }
+
+    fn record(&mut self, address: usize) {
+        if self.trace.is_none() && self.hooks.is_empty() {
+            return;
+        }
+
+        let entry = TraceEntry {
+            step: self.steps,
+            address,
+            instruction: self.program[address].clone(),
+            registers: self.registers.clone(),
+        };
+        for hook in self.hooks.iter_mut() {
+            hook(&entry)
+        }
+        if let Some(trace) = self.trace.as_mut() {
+            trace.push(entry)
+        }
+    }
+
+    /// Executes a single instruction, ignoring any breakpoints.
+    pub fn step(&mut self) -> StepResult {
+        self.paused_at = None;
+        if let Some(termination) = self.check_termination() {
+            return StepResult::Terminated(termination);
+        }
+
+        self.loop_detector.mark_seen(&self.registers);
+        self.steps += 1;
+
+        let address = self.registers.instruction_pointer;
+        let instruction = &self.program[address];
+        let operands = instruction.operands();
+        let registers = &mut self.registers;
+
+        // `None` signals that the instruction has overflowed
+        let next = Some(address as isize + 1);
+        let target = match instruction.opcode() {
+            Opcode::Nop => next,
+            Opcode::Acc => registers
+                .accumulator
+                .checked_add(registers.read(&operands[0]))
+                .map(|accumulator| registers.accumulator = accumulator)
+                .and(next),
+            Opcode::Jump => (address as isize).checked_add(registers.read(&operands[0])),
+            Opcode::Add => {
+                let value = registers.read(&operands[1]);
+                registers
+                    .modify(&operands[0], value, isize::checked_add)
+                    .and(next)
+            }
+            Opcode::Mul => {
+                let value = registers.read(&operands[1]);
+                registers
+                    .modify(&operands[0], value, isize::checked_mul)
+                    .and(next)
+            }
+            Opcode::JumpIfZero | Opcode::JumpIfNotZero => {
+                let is_zero = registers.read(&operands[0]) == 0;
+                if is_zero == (instruction.opcode() == Opcode::JumpIfZero) {
+                    (address as isize).checked_add(registers.read(&operands[1]))
+                } else {
+                    next
+                }
+            }
+            Opcode::Out => {
+                self.output.push(registers.read(&operands[0]));
+                next
+            }
+            Opcode::Halt => {
+                self.record(address);
+                let termination = Termination::Halted {
+                    accumulator: self.registers.accumulator,
+                    instruction_pointer: address,
+                };
+                return StepResult::Terminated(self.terminate(termination));
+            }
+        };
+
+        let target = match target {
+            Some(target) => target,
+            None => {
+                self.record(address);
+                let termination = Termination::Overflow {
+                    accumulator: self.registers.accumulator,
+                    instruction_pointer: address,
+                };
+                return StepResult::Terminated(self.terminate(termination));
+            }
+        };
+
+        // landing right after the last instruction is a valid termination
+        if target < 0 || target as usize > self.program.len() {
+            self.record(address);
+            let termination = Termination::JumpOutOfBounds {
+                accumulator: self.registers.accumulator,
+                instruction_pointer: address,
+                target,
+            };
+            return StepResult::Terminated(self.terminate(termination));
+        }
+
+        self.registers.instruction_pointer = target as usize;
+        self.record(address);
+        StepResult::Executed
+    }
+
+    /// Runs the program until it either terminates or reaches a breakpoint.
+    pub fn run(&mut self) -> RunResult {
+        loop {
+            if let Some(termination) = self.check_termination() {
+                return RunResult::Terminated(termination);
+            }
+
+            let address = self.registers.instruction_pointer;
+            if self.paused_at != Some(address) && self.is_breakpoint(address) {
+                self.paused_at = Some(address);
+                return RunResult::Breakpoint(address);
+            }
+
+            if let StepResult::Terminated(termination) = self.step() {
+                return RunResult::Terminated(termination);
+            }
+        }
+    }
+
+    /// Runs the program until it terminates, ignoring any breakpoints.
+    pub fn execute_program(&mut self) -> Termination {
+        loop {
+            if let StepResult::Terminated(termination) = self.step() {
+                return termination;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::convert::TryFrom;
+    use std::rc::Rc;
+
+    fn program(raw: &[&str]) -> Vec<Instruction> {
+        raw.iter()
+            .map(|raw| Instruction::try_from(&raw.to_string()).unwrap())
+            .collect()
+    }
+
+    fn sample_program() -> Vec<Instruction> {
+        program(&[
+            "nop +0", "acc +1", "jmp +4", "acc +3", "jmp -3", "acc -99", "acc +1", "jmp -4",
+            "acc +6",
+        ])
+    }
+
+    #[test]
+    fn stepping_through_the_program() {
+        let mut computer = Computer::new(sample_program());
+
+        assert_eq!(StepResult::Executed, computer.step());
+        assert_eq!(StepResult::Executed, computer.step());
+        assert_eq!(1, computer.accumulator());
+        assert_eq!(2, computer.instruction_pointer());
+        assert_eq!(2, computer.steps());
+    }
+
+    #[test]
+    fn loops_are_detected() {
+        let mut computer = Computer::new(sample_program());
+
+        assert_eq!(
+            Termination::LoopDetected {
+                accumulator: 5,
+                instruction_pointer: 1
+            },
+            computer.execute_program()
+        );
+    }
+
+    #[test]
+    fn breakpoints_on_index_and_opcode() {
+        let mut computer = Computer::new(sample_program());
+        computer.add_breakpoint(Breakpoint::Instruction(4));
+        computer.add_breakpoint(Breakpoint::Opcode(Opcode::Jump));
+
+        assert_eq!(RunResult::Breakpoint(2), computer.run());
+        assert_eq!(1, computer.accumulator());
+        // resuming does not stop at the same breakpoint again
+        assert_eq!(RunResult::Breakpoint(7), computer.run());
+        assert_eq!(RunResult::Breakpoint(4), computer.run());
+        assert_eq!(
+            RunResult::Terminated(Termination::LoopDetected {
+                accumulator: 5,
+                instruction_pointer: 1
+            }),
+            computer.run()
+        );
+    }
+
+    #[test]
+    fn out_of_bounds_jumps_are_reported() {
+        let mut computer = Computer::new(program(&["acc +2", "jmp -5"]));
+
+        assert_eq!(
+            Termination::JumpOutOfBounds {
+                accumulator: 2,
+                instruction_pointer: 1,
+                target: -4
+            },
+            computer.execute_program()
+        );
+    }
+
+    #[test]
+    fn step_limit_is_enforced() {
+        let mut computer = Computer::new(sample_program());
+        computer.set_step_limit(Some(3));
+
+        assert_eq!(
+            Termination::StepLimitExceeded {
+                accumulator: 1,
+                steps: 3
+            },
+            computer.execute_program()
+        );
+    }
+
+    #[test]
+    fn trace_and_hooks_see_every_instruction() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let hook_seen = Rc::clone(&seen);
+
+        let mut computer = Computer::new(program(&["acc +3", "nop +0", "acc -1"]));
+        computer.enable_trace();
+        computer.add_hook(move |entry| hook_seen.borrow_mut().push(entry.registers.accumulator));
+
+        assert_eq!(
+            Termination::Finished { accumulator: 2 },
+            computer.execute_program()
+        );
+        assert_eq!(vec![3, 3, 2], *seen.borrow());
+
+        let trace = computer.trace().unwrap();
+        assert_eq!(
+            vec![0, 1, 2],
+            trace.iter().map(|entry| entry.address).collect::<Vec<_>>()
+        );
+        assert_eq!(Opcode::Nop, trace[1].instruction.opcode());
+    }
+
+    #[test]
+    fn named_registers_and_output() {
+        let mut computer = Computer::new(program(&[
+            "add a +3",
+            "mul a +4",
+            "add acc a",
+            "out a",
+            "out acc",
+            "halt",
+            "acc +100",
+        ]));
+
+        assert_eq!(
+            Termination::Halted {
+                accumulator: 12,
+                instruction_pointer: 5
+            },
+            computer.execute_program()
+        );
+        assert_eq!(
+            12,
+            computer.registers().get(&Register::Named("a".to_string()))
+        );
+        assert_eq!(
+            0,
+            computer.registers().get(&Register::Named("b".to_string()))
+        );
+        assert_eq!(&[12, 12], computer.output());
+    }
+
+    #[test]
+    fn conditional_jumps_do_not_cause_false_loop_detection() {
+        // computes 5! by looping over the same instructions
+        let mut computer = Computer::new(program(&[
+            "add n +5",
+            "add acc +1",
+            "mul acc n",
+            "add n -1",
+            "jnz n -2",
+            "jz n +2",
+            "nop +0",
+        ]));
+
+        assert_eq!(
+            Termination::Finished { accumulator: 120 },
+            computer.execute_program()
+        );
+
+        let mut computer = Computer::new(program(&["add a +1", "jnz a +0"]));
+        assert_eq!(
+            Termination::LoopDetected {
+                accumulator: 0,
+                instruction_pointer: 1
+            },
+            computer.execute_program()
+        );
+    }
+
+    #[test]
+    fn overflows_are_reported() {
+        let mut computer = Computer::new(program(&["add a +2", "mul a a", "jnz a -1"]));
+        let termination = computer.execute_program();
+        assert_eq!(
+            Termination::Overflow {
+                accumulator: 0,
+                instruction_pointer: 1
+            },
+            termination
+        );
+        // the register keeps its last valid value
+        assert_eq!(
+            1 << 32,
+            computer.registers().get(&Register::Named("a".to_string()))
+        );
+
+        let mut computer = Computer::new(program(&["acc +9223372036854775807", "acc +1"]));
+        assert_eq!(
+            Termination::Overflow {
+                accumulator: isize::MAX,
+                instruction_pointer: 1
+            },
+            computer.execute_program()
+        );
+    }
+
+    #[test]
+    fn terminations_are_sticky() {
+        let mut computer = Computer::new(program(&["acc +1", "halt"]));
+        let halted = Termination::Halted {
+            accumulator: 1,
+            instruction_pointer: 1,
+        };
+
+        assert_eq!(halted, computer.execute_program());
+        assert_eq!(StepResult::Terminated(halted), computer.step());
+        assert_eq!(RunResult::Terminated(halted), computer.run());
+        assert_eq!(Some(halted), computer.termination());
+
+        let mut computer = Computer::new(program(&["acc +2", "jmp -5"]));
+        let out_of_bounds = computer.execute_program();
+        assert!(matches!(out_of_bounds, Termination::JumpOutOfBounds { .. }));
+        assert_eq!(RunResult::Terminated(out_of_bounds), computer.run());
+        assert_eq!(out_of_bounds, computer.execute_program());
+
+        computer.reset();
+        assert_eq!(None, computer.termination());
+        assert_eq!(StepResult::Executed, computer.step());
+    }
+
+    #[test]
+    fn raising_step_limit_resumes_execution() {
+        let mut computer = Computer::new(sample_program());
+        computer.set_step_limit(Some(3));
+        assert!(matches!(
+            computer.execute_program(),
+            Termination::StepLimitExceeded { .. }
+        ));
+
+        computer.set_step_limit(None);
+        assert!(matches!(
+            computer.execute_program(),
+            Termination::LoopDetected { .. }
+        ));
+    }
+
+    #[test]
+    fn edited_programs_use_the_right_loop_detection() {
+        let mut computer = Computer::new(program(&["add n +2", "add n -1", "add n -1"]));
+        computer
+            .edit_instruction(2, |instruction| {
+                instruction.set_opcode(Opcode::JumpIfNotZero)
+            })
+            .unwrap()
+            .unwrap();
+
+        // the second instruction gets executed twice without looping
+        assert_eq!(
+            Termination::Finished { accumulator: 0 },
+            computer.execute_program()
+        );
+        assert_eq!(None, computer.edit_instruction(3, |_| ()));
+    }
+}
@@ -0,0 +1,432 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::fmt::{self, Debug, Display, Formatter};
+
+const ACCUMULATOR_NAME: &str = "acc";
+
+/// Kind of value expected by an opcode in given operand position.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum OperandKind {
+    /// Name of the register to modify.
+    Register,
+    /// Either an immediate value or a register to read from.
+    Value,
+    /// Immediate offset relative to the current instruction or, in the assembly, a label.
+    Offset,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Opcode {
+    Acc,
+    Jump,
+    Nop,
+    Add,
+    Mul,
+    JumpIfZero,
+    JumpIfNotZero,
+    Out,
+    Halt,
+}
+
+impl Opcode {
+    pub const ALL: [Opcode; 9] = [
+        Opcode::Acc,
+        Opcode::Jump,
+        Opcode::Nop,
+        Opcode::Add,
+        Opcode::Mul,
+        Opcode::JumpIfZero,
+        Opcode::JumpIfNotZero,
+        Opcode::Out,
+        Opcode::Halt,
+    ];
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            Opcode::Acc => "acc",
+            Opcode::Jump => "jmp",
+            Opcode::Nop => "nop",
+            Opcode::Add => "add",
+            Opcode::Mul => "mul",
+            Opcode::JumpIfZero => "jz",
+            Opcode::JumpIfNotZero => "jnz",
+            Opcode::Out => "out",
+            Opcode::Halt => "halt",
+        }
+    }
+
+    pub fn operand_kinds(&self) -> &'static [OperandKind] {
+        match self {
+            Opcode::Acc | Opcode::Out => &[OperandKind::Value],
+            // nop takes an offset so that it could be freely swapped with jmp
+            Opcode::Jump | Opcode::Nop => &[OperandKind::Offset],
+            Opcode::Add | Opcode::Mul => &[OperandKind::Register, OperandKind::Value],
+            Opcode::JumpIfZero | Opcode::JumpIfNotZero => {
+                &[OperandKind::Value, OperandKind::Offset]
+            }
+            Opcode::Halt => &[],
+        }
+    }
+}
+
+impl Debug for Opcode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.mnemonic())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct InvalidOpcode(pub String);
+
+impl<'a> TryFrom<&'a str> for Opcode {
+    type Error = InvalidOpcode;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Opcode::ALL
+            .iter()
+            .find(|opcode| opcode.mnemonic() == value)
+            .copied()
+            .ok_or_else(|| InvalidOpcode(value.to_owned()))
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum Register {
+    Accumulator,
+    Named(String),
+}
+
+impl Debug for Register {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Register::Accumulator => write!(f, "{}", ACCUMULATOR_NAME),
+            Register::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl Register {
+    fn parse(raw: &str) -> Option<Self> {
+        if raw == ACCUMULATOR_NAME {
+            Some(Register::Accumulator)
+        } else if is_identifier(raw) {
+            Some(Register::Named(raw.to_owned()))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub enum Operand {
+    Immediate(isize),
+    Register(Register),
+}
+
+impl Debug for Operand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Immediate(value) => write!(f, "{}", value),
+            Operand::Register(register) => write!(f, "{:?}", register),
+        }
+    }
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Immediate(value) => write!(f, "{:+}", value),
+            Operand::Register(register) => write!(f, "{:?}", register),
+        }
+    }
+}
+
+pub(crate) fn is_identifier(raw: &str) -> bool {
+    let mut chars = raw.chars();
+    matches!(chars.next(), Some(first) if first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum InvalidInstruction {
+    MalformedOperand(String),
+    MalformedOpcode(InvalidOpcode),
+    TooShortInstruction,
+    TooManyOperands,
+    UnknownLabel(String),
+}
+
+impl From<InvalidOpcode> for InvalidInstruction {
+    fn from(err: InvalidOpcode) -> Self {
+        InvalidInstruction::MalformedOpcode(err)
+    }
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Instruction {
+    // private so that every instruction has gone through the operand validation
+    opcode: Opcode,
+    operands: Vec<Operand>,
+}
+
+impl Debug for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.opcode)?;
+        for operand in self.operands.iter() {
+            write!(f, " {:?}", operand)?;
+        }
+        Ok(())
+    }
+}
+
+// canonical form of the instruction, as understood by the parser
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.opcode)?;
+        for operand in self.operands.iter() {
+            write!(f, " {}", operand)?;
+        }
+        Ok(())
+    }
+}
+
+impl Instruction {
+    /// Creates new instruction making sure the operands match what the opcode expects.
+    pub fn new(opcode: Opcode, operands: Vec<Operand>) -> Result<Self, InvalidInstruction> {
+        Self::validate_operands(opcode, &operands)?;
+        Ok(Instruction { opcode, operands })
+    }
+
+    fn validate_operands(opcode: Opcode, operands: &[Operand]) -> Result<(), InvalidInstruction> {
+        let kinds = opcode.operand_kinds();
+        if operands.len() < kinds.len() {
+            return Err(InvalidInstruction::TooShortInstruction);
+        }
+        if operands.len() > kinds.len() {
+            return Err(InvalidInstruction::TooManyOperands);
+        }
+
+        for (operand, kind) in operands.iter().zip(kinds.iter()) {
+            let valid = matches!(
+                (kind, operand),
+                (OperandKind::Value, _)
+                    | (OperandKind::Register, Operand::Register(_))
+                    | (OperandKind::Offset, Operand::Immediate(_))
+            );
+            if !valid {
+                return Err(InvalidInstruction::MalformedOperand(format!(
+                    "{:?} is not a valid {:?} operand of {:?}",
+                    operand, kind, opcode
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn opcode(&self) -> Opcode {
+        self.opcode
+    }
+
+    pub fn operands(&self) -> &[Operand] {
+        &self.operands
+    }
+
+    /// Replaces the opcode, as long as the existing operands are also valid for the new one.
+    pub fn set_opcode(&mut self, opcode: Opcode) -> Result<(), InvalidInstruction> {
+        Self::validate_operands(opcode, &self.operands)?;
+        self.opcode = opcode;
+        Ok(())
+    }
+
+    /// Parses the instruction using the provided closure to translate labels into offsets.
+    pub(crate) fn parse_with_labels<F>(
+        raw: &str,
+        resolve_label: F,
+    ) -> Result<Self, InvalidInstruction>
+    where
+        F: Fn(&str) -> Option<isize>,
+    {
+        let mut tokens = raw.split_ascii_whitespace();
+        let opcode = Opcode::try_from(
+            tokens
+                .next()
+                .ok_or(InvalidInstruction::TooShortInstruction)?,
+        )?;
+
+        let kinds = opcode.operand_kinds();
+        let raw_operands: Vec<_> = tokens.collect();
+        if raw_operands.len() < kinds.len() {
+            return Err(InvalidInstruction::TooShortInstruction);
+        }
+        if raw_operands.len() > kinds.len() {
+            return Err(InvalidInstruction::TooManyOperands);
+        }
+
+        let operands = raw_operands
+            .into_iter()
+            .zip(kinds.iter())
+            .map(|(raw_operand, kind)| parse_operand(raw_operand, *kind, &resolve_label))
+            .collect::<Result<_, _>>()?;
+
+        Instruction::new(opcode, operands)
+    }
+}
+
+fn parse_operand<F>(
+    raw: &str,
+    kind: OperandKind,
+    resolve_label: F,
+) -> Result<Operand, InvalidInstruction>
+where
+    F: Fn(&str) -> Option<isize>,
+{
+    if let Ok(value) = raw.parse() {
+        return Ok(Operand::Immediate(value));
+    }
+
+    match kind {
+        OperandKind::Value | OperandKind::Register => Register::parse(raw).map(Operand::Register),
+        OperandKind::Offset if is_identifier(raw) => {
+            return resolve_label(raw)
+                .map(Operand::Immediate)
+                .ok_or_else(|| InvalidInstruction::UnknownLabel(raw.to_owned()))
+        }
+        OperandKind::Offset => None,
+    }
+    .ok_or_else(|| InvalidInstruction::MalformedOperand(format!("{} is malformed", raw)))
+}
+
+impl<'a> TryFrom<&'a str> for Instruction {
+    type Error = InvalidInstruction;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Instruction::parse_with_labels(value, |_| None)
+    }
+}
+
+impl<'a> TryFrom<&'a String> for Instruction {
+    type Error = InvalidInstruction;
+
+    fn try_from(value: &'a String) -> Result<Self, Self::Error> {
+        Instruction::try_from(value.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instruction_formatting() {
+        assert_eq!(
+            "jmp 2",
+            format!(
+                "{:?}",
+                Instruction {
+                    opcode: Opcode::Jump,
+                    operands: vec![Operand::Immediate(2)]
+                }
+            )
+        );
+
+        assert_eq!(
+            "nop -3",
+            format!(
+                "{:?}",
+                Instruction {
+                    opcode: Opcode::Nop,
+                    operands: vec![Operand::Immediate(-3)]
+                }
+            )
+        );
+
+        assert_eq!(
+            "acc 5",
+            format!(
+                "{:?}",
+                Instruction {
+                    opcode: Opcode::Acc,
+                    operands: vec![Operand::Immediate(5)]
+                }
+            )
+        )
+    }
+
+    #[test]
+    fn canonical_formatting() {
+        for raw in &[
+            "acc +5",
+            "jmp -3",
+            "add b -2",
+            "mul acc x",
+            "jnz a +4",
+            "out +0",
+            "halt",
+        ] {
+            assert_eq!(*raw, Instruction::try_from(*raw).unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn invalid_instructions() {
+        assert_eq!(
+            Err(InvalidInstruction::TooShortInstruction),
+            Instruction::try_from(&"nop".to_string())
+        );
+        assert_eq!(
+            Err(InvalidInstruction::MalformedOpcode(InvalidOpcode(
+                "foo".to_string()
+            ))),
+            Instruction::try_from(&"foo +1".to_string())
+        );
+        assert!(matches!(
+            Instruction::try_from(&"acc 1one".to_string()),
+            Err(InvalidInstruction::MalformedOperand(_))
+        ));
+        assert!(matches!(
+            Instruction::try_from("add +1 +2"),
+            Err(InvalidInstruction::MalformedOperand(_))
+        ));
+        assert_eq!(
+            Err(InvalidInstruction::TooManyOperands),
+            Instruction::try_from("halt +1")
+        );
+        assert_eq!(
+            Err(InvalidInstruction::UnknownLabel("start".to_string())),
+            Instruction::try_from("jmp start")
+        );
+    }
+
+    #[test]
+    fn opcode_changes_are_validated() {
+        let mut instruction = Instruction::try_from("nop +4").unwrap();
+        assert_eq!(Ok(()), instruction.set_opcode(Opcode::Jump));
+        assert_eq!(Opcode::Jump, instruction.opcode());
+        assert_eq!(&[Operand::Immediate(4)], instruction.operands());
+
+        assert_eq!(
+            Err(InvalidInstruction::TooShortInstruction),
+            instruction.set_opcode(Opcode::Add)
+        );
+        assert_eq!(
+            Err(InvalidInstruction::TooManyOperands),
+            instruction.set_opcode(Opcode::Halt)
+        );
+        // the instruction is left as it was
+        assert_eq!(Opcode::Jump, instruction.opcode());
+    }
+}
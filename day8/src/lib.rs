@@ -0,0 +1,139 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::Infallible;
+use std::convert::TryFrom;
+use std::path::Path;
+use utils::input_read::{self, InputError};
+use utils::solution::Solution;
+
+pub use analysis::{lint, ControlFlowGraph, Lint};
+pub use assembler::{assemble, disassemble, AssemblyError, AssemblyErrorKind};
+pub use computer::{
+    Breakpoint, Computer, Registers, RunResult, StepResult, Termination, TraceEntry,
+};
+pub use instruction::{
+    Instruction, InvalidInstruction, InvalidOpcode, Opcode, Operand, OperandKind, Register,
+};
+
+mod analysis;
+mod assembler;
+mod computer;
+mod instruction;
+
+fn parse_as_instructions(raw: &[String]) -> Vec<Instruction> {
+    raw.iter()
+        .map(|raw_instruction| {
+            Instruction::try_from(raw_instruction).expect("failed to parse instruction")
+        })
+        .collect()
+}
+
+pub fn part1(input: &[String]) -> isize {
+    let instructions = parse_as_instructions(input);
+    match Computer::new(instructions).execute_program() {
+        Termination::LoopDetected { accumulator, .. } => accumulator,
+        termination => panic!("failed to loop in part1 - {:?}", termination),
+    }
+}
+
+fn substitute_instruction(instruction: &mut Instruction) -> bool {
+    let substitute = match instruction.opcode() {
+        Opcode::Nop => Opcode::Jump,
+        Opcode::Jump => Opcode::Nop,
+        _ => return false,
+    };
+
+    instruction
+        .set_opcode(substitute)
+        .expect("nop and jmp take the same operands");
+    true
+}
+
+pub fn part2(input: &[String]) -> Option<isize> {
+    let mut instructions = parse_as_instructions(input);
+    let flip = ControlFlowGraph::new(&instructions).terminating_flip()?;
+    substitute_instruction(&mut instructions[flip]);
+
+    let termination = Computer::new(instructions).execute_program();
+    if termination.is_finished() {
+        Some(termination.accumulator())
+    } else {
+        None
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<String>;
+    type ParseError = Infallible;
+    type Part1Output = isize;
+    type Part2Output = isize;
+
+    fn parse_input<P: AsRef<Path>>(path: P) -> Result<Self::Input, InputError<Self::ParseError>> {
+        input_read::read_line_input(path)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Part1Output> {
+        Some(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2Output> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_sample_input() {
+        let input = vec![
+            "nop +0".to_string(),
+            "acc +1".to_string(),
+            "jmp +4".to_string(),
+            "acc +3".to_string(),
+            "jmp -3".to_string(),
+            "acc -99".to_string(),
+            "acc +1".to_string(),
+            "jmp -4".to_string(),
+            "acc +6".to_string(),
+        ];
+
+        let expected = 5;
+
+        assert_eq!(expected, part1(&input))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = vec![
+            "nop +0".to_string(),
+            "acc +1".to_string(),
+            "jmp +4".to_string(),
+            "acc +3".to_string(),
+            "jmp -3".to_string(),
+            "acc -99".to_string(),
+            "acc +1".to_string(),
+            "jmp -4".to_string(),
+            "acc +6".to_string(),
+        ];
+
+        let expected = 8;
+
+        assert_eq!(expected, part2(&input).unwrap())
+    }
+}
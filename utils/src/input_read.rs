@@ -12,40 +12,136 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt::Debug;
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
 use std::fs::{self, File};
-use std::io::{self, BufRead};
+use std::io::{self, BufRead, BufReader, Read};
+use std::marker::PhantomData;
+use std::mem;
 use std::path::Path;
 use std::str::FromStr;
 
-/// Reads the file as lines, parsing each of them into desired type.
-pub fn read_line_input<T, P>(path: P) -> io::Result<Vec<T>>
+/// Error returned when reading the input fails, either because the file couldn't be read
+/// or because one of its lines couldn't be parsed into the desired type.
+#[derive(Debug)]
+pub enum InputError<E = Infallible> {
+    Io(io::Error),
+    Parse {
+        // 1-based number of the offending line
+        line: usize,
+        text: String,
+        err: E,
+    },
+}
+
+impl<E> From<io::Error> for InputError<E> {
+    fn from(err: io::Error) -> Self {
+        InputError::Io(err)
+    }
+}
+
+impl<E: Debug> Display for InputError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Io(err) => write!(f, "failed to read the input - {}", err),
+            InputError::Parse { line, text, err } => write!(
+                f,
+                "line {} ({:?}) could not be parsed into desired type - {:?}",
+                line, text, err
+            ),
+        }
+    }
+}
+
+impl<E: Debug> Error for InputError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Io(err) => Some(err),
+            InputError::Parse { .. } => None,
+        }
+    }
+}
+
+/// Lazily parses lines of the underlying reader into desired type.
+pub struct LineInput<T, R> {
+    lines: io::Lines<R>,
+    line_number: usize,
+    _parsed: PhantomData<T>,
+}
+
+impl<T, R> Iterator for LineInput<T, R>
 where
-    P: AsRef<Path>,
     T: FromStr,
-    <T as FromStr>::Err: Debug,
+    R: BufRead,
 {
-    let file = File::open(path)?;
+    type Item = Result<T, InputError<<T as FromStr>::Err>>;
 
-    let lines = io::BufReader::new(file).lines();
-    let size_hint = lines.size_hint();
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.lines.next()?;
+        self.line_number += 1;
+        let line_number = self.line_number;
 
-    // use upper bound size hint if available, otherwise use lower bound
-    let mut results = Vec::with_capacity(size_hint.1.unwrap_or(size_hint.0));
-    for line in lines {
-        // the last one is technically not an io error, but I can't be bothered to create a separate error type just for this
-        results.push(line?.parse().map_err(|parse_err| {
-            io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!(
-                    "input could not be parsed into desired type - {:?}",
-                    parse_err
-                ),
-            )
-        })?)
+        Some(line.map_err(InputError::Io).and_then(|line| {
+            line.parse().map_err(|err| InputError::Parse {
+                line: line_number,
+                text: line,
+                err,
+            })
+        }))
     }
+}
 
-    Ok(results)
+/// Opens the file and returns an iterator parsing each of its lines into desired type
+/// as they are being read.
+pub fn iter_line_input<T, P>(path: P) -> io::Result<LineInput<T, BufReader<File>>>
+where
+    P: AsRef<Path>,
+    T: FromStr,
+{
+    Ok(iter_line_input_from_reader(BufReader::new(File::open(
+        path,
+    )?)))
+}
+
+/// Returns an iterator parsing each line of the reader into desired type as they are being read.
+pub fn iter_line_input_from_reader<T, R>(reader: R) -> LineInput<T, R>
+where
+    R: BufRead,
+    T: FromStr,
+{
+    LineInput {
+        lines: reader.lines(),
+        line_number: 0,
+        _parsed: PhantomData,
+    }
+}
+
+/// Reads the file as lines, parsing each of them into desired type.
+pub fn read_line_input<T, P>(path: P) -> Result<Vec<T>, InputError<<T as FromStr>::Err>>
+where
+    P: AsRef<Path>,
+    T: FromStr,
+{
+    iter_line_input(path)?.collect()
+}
+
+/// Reads the reader as lines, parsing each of them into desired type.
+pub fn read_line_input_from_reader<T, R>(
+    reader: R,
+) -> Result<Vec<T>, InputError<<T as FromStr>::Err>>
+where
+    R: BufRead,
+    T: FromStr,
+{
+    iter_line_input_from_reader(reader).collect()
+}
+
+/// Parses each line of the provided string into desired type.
+pub fn parse_line_input<T: FromStr>(
+    input: &str,
+) -> Result<Vec<T>, InputError<<T as FromStr>::Err>> {
+    read_line_input_from_reader(input.as_bytes())
 }
 
 /// Reads the file as a String
@@ -53,8 +149,173 @@ pub fn read_to_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
     fs::read_to_string(path)
 }
 
+/// Reads the entire reader as a String
+pub fn read_to_string_from_reader<R: Read>(mut reader: R) -> io::Result<String> {
+    let mut string = String::new();
+    reader.read_to_string(&mut string)?;
+    Ok(string)
+}
+
 /// Reads the file and outputs String groups that were originally separated by an empty line
 pub fn read_into_string_groups<P: AsRef<Path>>(path: P) -> io::Result<Vec<String>> {
-    fs::read_to_string(path)
-        .map(|string| string.split("\n\n").map(|split| split.to_owned()).collect())
+    fs::read_to_string(path).map(|string| split_into_string_groups(&string))
+}
+
+/// Reads the entire reader and outputs String groups that were originally separated by an empty line
+pub fn read_into_string_groups_from_reader<R: Read>(reader: R) -> io::Result<Vec<String>> {
+    read_to_string_from_reader(reader).map(|string| split_into_string_groups(&string))
+}
+
+/// Splits the provided string into groups that were originally separated by an empty line
+pub fn split_into_string_groups(input: &str) -> Vec<String> {
+    split_into_line_groups(input)
+        .into_iter()
+        .map(|lines| lines.join("\n"))
+        .collect()
+}
+
+/// Reads the file and outputs groups of lines that were originally separated by an empty line
+pub fn read_into_line_groups<P: AsRef<Path>>(path: P) -> io::Result<Vec<Vec<String>>> {
+    fs::read_to_string(path).map(|string| split_into_line_groups(&string))
+}
+
+/// Reads the entire reader and outputs groups of lines that were originally separated by an empty line
+pub fn read_into_line_groups_from_reader<R: Read>(reader: R) -> io::Result<Vec<Vec<String>>> {
+    read_to_string_from_reader(reader).map(|string| split_into_line_groups(&string))
+}
+
+/// Splits the provided string into groups of lines that were originally separated by an empty line.
+/// Both `\n` and `\r\n` line endings are accepted, any run of blank lines is treated
+/// as a single separator and no empty groups are ever produced.
+pub fn split_into_line_groups(input: &str) -> Vec<Vec<String>> {
+    let mut groups = Vec::new();
+    let mut current_group = Vec::new();
+
+    // `lines` already takes care of stripping the `\r` of the windows line endings
+    for line in input.lines() {
+        if line.trim().is_empty() {
+            if !current_group.is_empty() {
+                groups.push(mem::take(&mut current_group));
+            }
+        } else {
+            current_group.push(line.to_owned());
+        }
+    }
+
+    if !current_group.is_empty() {
+        groups.push(current_group);
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::num::ParseIntError;
+    use std::path::PathBuf;
+
+    fn write_temp_input(name: &str, content: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("utils-input-read-{}", name));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn reading_parsed_lines() {
+        let path = write_temp_input("parsed-lines", "1\n2\n3");
+        let read: Vec<usize> = read_line_input(&path).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(vec![1, 2, 3], read);
+    }
+
+    #[test]
+    fn missing_file_is_an_io_error() {
+        let err: InputError<ParseIntError> =
+            read_line_input::<usize, _>("this-file-does-not-exist").unwrap_err();
+
+        assert!(matches!(err, InputError::Io(_)))
+    }
+
+    #[test]
+    fn parse_error_points_at_the_offending_line() {
+        let err = parse_line_input::<usize>("1\n2\nfoo\n4").unwrap_err();
+
+        match err {
+            InputError::Parse { line, text, err } => {
+                assert_eq!(3, line);
+                assert_eq!("foo", text);
+                assert_eq!("foo".parse::<usize>().unwrap_err(), err);
+            }
+            InputError::Io(err) => panic!("unexpected io error - {}", err),
+        }
+    }
+
+    #[test]
+    fn lazy_line_input_keeps_going_after_bad_line() {
+        let mut lines = iter_line_input_from_reader::<usize, _>("1\nfoo\n3".as_bytes());
+
+        assert_eq!(1, lines.next().unwrap().unwrap());
+        assert!(matches!(
+            lines.next().unwrap(),
+            Err(InputError::Parse { line: 2, .. })
+        ));
+        assert_eq!(3, lines.next().unwrap().unwrap());
+        assert!(lines.next().is_none());
+    }
+
+    #[test]
+    fn reading_from_reader() {
+        let reader = io::Cursor::new("foo\nbar");
+        let read: Vec<String> = read_line_input_from_reader(reader).unwrap();
+
+        assert_eq!(vec!["foo".to_string(), "bar".to_string()], read);
+        assert_eq!(
+            "foo\nbar",
+            read_to_string_from_reader("foo\nbar".as_bytes()).unwrap()
+        );
+    }
+
+    #[test]
+    fn splitting_string_groups() {
+        let expected = vec!["a\nb".to_string(), "c".to_string()];
+
+        assert_eq!(expected, split_into_string_groups("a\nb\n\nc"));
+        assert_eq!(
+            expected,
+            read_into_string_groups_from_reader("a\nb\n\nc".as_bytes()).unwrap()
+        );
+    }
+
+    #[test]
+    fn splitting_string_groups_with_windows_line_endings() {
+        let expected = vec!["a\nb".to_string(), "c".to_string()];
+
+        assert_eq!(expected, split_into_string_groups("a\r\nb\r\n\r\nc\r\n"));
+    }
+
+    #[test]
+    fn splitting_string_groups_ignores_redundant_blank_lines() {
+        let expected = vec!["a\nb".to_string(), "c".to_string()];
+
+        assert_eq!(expected, split_into_string_groups("\na\nb\n\n\n\nc\n\n"));
+        assert_eq!(expected, split_into_string_groups("a\nb\n  \n\t\nc\n"));
+        assert!(split_into_string_groups("\n\n\n").is_empty());
+    }
+
+    #[test]
+    fn splitting_line_groups() {
+        let expected = vec![
+            vec!["a".to_string(), "b".to_string()],
+            vec!["c".to_string()],
+        ];
+
+        assert_eq!(expected, split_into_line_groups("a\nb\n\n\nc\n"));
+        assert_eq!(
+            expected,
+            read_into_line_groups_from_reader("a\r\nb\r\n\r\nc".as_bytes()).unwrap()
+        );
+    }
 }
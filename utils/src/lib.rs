@@ -13,3 +13,4 @@
 // limitations under the License.
 
 pub mod input_read;
+pub mod solution;
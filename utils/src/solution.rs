@@ -0,0 +1,44 @@
+// Copyright 2020 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::input_read::InputError;
+use std::fmt::{Debug, Display};
+use std::path::Path;
+
+/// Common interface exposed by every day so that they could be run without knowing
+/// their concrete input and output types.
+pub trait Solution {
+    /// Parsed form of the puzzle input.
+    type Input;
+    /// Error produced when a line of the input could not be parsed.
+    type ParseError: Debug;
+    type Part1Output: Display;
+    type Part2Output: Display;
+
+    /// Whether the day has a second part to solve at all, i.e. the final day does not.
+    const HAS_PART2: bool = true;
+
+    /// Reads the puzzle input located at the provided path.
+    fn parse_input<P: AsRef<Path>>(path: P) -> Result<Self::Input, InputError<Self::ParseError>>;
+
+    /// Problems with the input that were not severe enough to fail the parsing,
+    /// such as lines that were skipped, for the caller to report.
+    fn input_warnings(_input: &Self::Input) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Part1Output>;
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2Output>;
+}